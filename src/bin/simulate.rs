@@ -1,18 +1,697 @@
-use pirate_finops::resources::Difficulty;
-use pirate_finops::simulator::run_simulation_suite;
-
-fn main() {
-    println!("\n🏴‍☠️ Running Pirate FinOps Balance Simulation...\n");
-
-    println!("Testing Strategic Gameplay (Normal Difficulty Only):");
-    println!("====================================================");
-    let report = run_simulation_suite(100, Difficulty::Normal);
-    report.print();
-
-    println!("\n🎯 Strategic Analysis:");
-    println!("  - Critical resources (AKS, Reservations): $300-500/mo savings");
-    println!("  - High-value resources (VMs, App Services): $150-250/mo savings");
-    println!("  - Medium resources (Logs): $120-150/mo savings");
-    println!("  - Low-priority resources (Disks, IPs): $15-40/mo savings");
-    println!("  - Players must prioritize high-value fixes and skip low-value ones");
-}
\ No newline at end of file
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::process::ExitCode;
+use std::sync::Arc;
+use serde::Serialize;
+use pirate_finops::catalog::ResourceCatalog;
+use pirate_finops::config::GameConfig;
+use pirate_finops::engine::GameEngine;
+use pirate_finops::resources::{Difficulty, GameData, GameRng};
+use pirate_finops::simulator::{
+    default_threads, print_difficulty_summary, print_head_to_head, run_matchup, GameResult, GameSink, Matchup,
+    SimulationReport, TARGET_WIN_RATE,
+};
+use pirate_finops::solver::{Solution, SolveGoal, Solver, PAR_NODE_LIMIT};
+use pirate_finops::strategy::StrategyKind;
+use pirate_finops::tuner::{TuneBounds, TuneParameter, TuneResult, TuneStep, Tuner};
+
+const USAGE: &str = "Usage: simulate [OPTIONS]
+
+Plays the game headlessly with a bot and reports balance statistics.
+
+Options:
+  -n, --games <N>                 Games per difficulty [default: 100]
+  -s, --seed <SEED>               First seed, games are played on SEED, SEED+1, ... [default: 0]
+      --seeds <FROM..TO>          Play exactly these seeds (TO excluded), instead of --games/--seed
+  -d, --difficulty <NAME>         easy, normal, hard, nightmare or all [default: all]
+      --strategy <NAMES>          Bots that play the games, comma-separated or all; several bots
+                                  are compared head-to-head on the same seeds [default: greedy]
+                                  (greedy, rush, cluster, combo, balanced, random, search)
+  -c, --config <PATH>             Game config file, repeat to compare configs on the same seeds
+                                  [default: the shipped config]
+      --catalog <PATH>            Resource catalog file [default: the shipped catalog]
+  -f, --format <FORMAT>           text, json or csv [default: text]
+  -j, --threads <N>               Worker threads [default: one per core]
+      --games-csv <PATH>          Also write one CSV row per game to PATH
+      --assert-win-rate <MIN..MAX>
+                                  Fail unless every win rate is within MIN..MAX percent
+  -h, --help                      Print this help
+
+Tuning (searches each selected preset for settings that hit the target band):
+      --tune <PARAM=MIN..MAX>     Let PARAM move between MIN and MAX, repeat for more knobs; they
+                                  are tuned in the order given (budget, burn_coefficient,
+                                  move_cost, resource_density)
+      --target <MIN..MAX>         Win rate band to aim for, in percent [default: 20..30]
+  -o, --output <PATH>             Where to write the tuned config [default: tuned.config.ron]
+
+Solving (searches for each seed's best plan with the whole map and every roll known):
+      --solve                     Report each seed's par instead of playing bots; with a single
+                                  seed the plan is printed too
+      --goal <GOAL>               fastest (win soonest) or richest (win with the most budget)
+                                  [default: fastest]
+      --nodes <N>                 Game states explored per seed before settling for the best
+                                  plan found; a par cut short isn't proven optimal
+                                  [default: 50000, the limit the game uses]
+
+Exit status: 0 on success, 1 if a win rate is outside --assert-win-rate or tuning didn't reach
+the target, 2 on bad arguments or config.";
+
+// Passes over every knob, and bisection steps per knob per pass, before the tuner gives up
+const TUNE_ROUNDS: u32 = 3;
+const TUNE_BISECTION_STEPS: u32 = 8;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+struct Options {
+    seeds: Range<u64>,
+    difficulties: Vec<Difficulty>,
+    strategies: Vec<StrategyKind>,
+    configs: Vec<(String, Arc<GameConfig>)>,
+    catalog: Arc<ResourceCatalog>,
+    format: Format,
+    threads: usize,
+    games_csv: Option<String>,
+    win_rate_band: Option<(f32, f32)>,
+    tune: Vec<TuneBounds>,
+    target: (f32, f32),
+    output: String,
+    solve: bool,
+    goal: SolveGoal,
+    nodes: u64,
+}
+
+// Every bot on one difficulty under one config
+struct Run {
+    config: String,
+    matchup: Matchup,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("simulate: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if !options.tune.is_empty() {
+        return tune(&options);
+    }
+    if options.solve {
+        return solve(&options);
+    }
+
+    // Per-game rows are streamed to the file as chunks of games finish, in seed order
+    let mut games_csv = match &options.games_csv {
+        Some(path) => match create_games_csv(path) {
+            Ok(file) => Some((path, file)),
+            Err(err) => {
+                eprintln!("simulate: could not write {}: {}", path, err);
+                return ExitCode::from(2);
+            }
+        },
+        None => None,
+    };
+    let mut write_error = None;
+
+    let mut runs = Vec::new();
+    for (name, config) in &options.configs {
+        for &difficulty in &options.difficulties {
+            let games = options.seeds.end - options.seeds.start;
+            eprintln!("{} on {} ({} games per bot)...", difficulty.name(), name, games);
+            let streaming = games_csv.is_some();
+            let mut write_games = |games: &[GameResult]| {
+                if let Some((_, file)) = games_csv.as_mut() {
+                    for game in games {
+                        if let Err(err) = writeln!(file, "{},{}", csv_field(name), game.csv_row()) {
+                            write_error.get_or_insert(err);
+                        }
+                    }
+                }
+            };
+            let on_games: Option<&mut GameSink> = if streaming { Some(&mut write_games) } else { None };
+            let matchup = run_matchup(
+                difficulty,
+                &options.strategies,
+                options.seeds.clone(),
+                config,
+                &options.catalog,
+                options.threads,
+                on_games,
+            );
+            runs.push(Run {
+                config: name.clone(),
+                matchup,
+            });
+        }
+    }
+
+    if let Some((path, mut file)) = games_csv {
+        if let Some(err) = write_error.or_else(|| file.flush().err()) {
+            eprintln!("simulate: could not write {}: {}", path, err);
+            return ExitCode::from(2);
+        }
+    }
+
+    match options.format {
+        Format::Text => print_text(&options, &runs),
+        Format::Json => print_json(&runs),
+        Format::Csv => print_csv(&runs),
+    }
+
+    let Some((min, max)) = options.win_rate_band else {
+        return ExitCode::SUCCESS;
+    };
+    let mut in_band = true;
+    for run in &runs {
+        for report in &run.matchup.reports {
+            if report.win_rate < min || report.win_rate > max {
+                eprintln!(
+                    "❌ {} bot, {} on {}: win rate {:.1}% is outside {:.1}-{:.1}%",
+                    report.strategy.name(),
+                    report.difficulty.name(),
+                    run.config,
+                    report.win_rate,
+                    min,
+                    max
+                );
+                in_band = false;
+            }
+        }
+    }
+    if in_band {
+        eprintln!("✅ Every win rate is within {:.1}-{:.1}%", min, max);
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// Ok(None) means help was asked for
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut games: u64 = 100;
+    let mut seed: u64 = 0;
+    let mut seed_range = None;
+    let mut difficulties = Difficulty::ALL.to_vec();
+    let mut strategies = vec![StrategyKind::Greedy];
+    let mut config_paths = Vec::new();
+    let mut catalog_path = None;
+    let mut format = Format::Text;
+    let mut threads = default_threads();
+    let mut games_csv = None;
+    let mut win_rate_band = None;
+    let mut tune = Vec::new();
+    let mut target = TARGET_WIN_RATE;
+    let mut output = "tuned.config.ron".to_string();
+    let mut solve = false;
+    let mut goal = SolveGoal::Fastest;
+    let mut nodes = PAR_NODE_LIMIT;
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-n" | "--games" => games = parse_number(&value(&arg)?, &arg)?,
+            "-s" | "--seed" => seed = parse_number(&value(&arg)?, &arg)?,
+            "--seeds" => {
+                let (from, to) = parse_range(&value(&arg)?, &arg)?;
+                if from >= to {
+                    return Err(format!("{} range {}..{} is empty", arg, from, to));
+                }
+                seed_range = Some(from..to);
+            }
+            "-d" | "--difficulty" => {
+                let name = value(&arg)?;
+                difficulties = if name.eq_ignore_ascii_case("all") {
+                    Difficulty::ALL.to_vec()
+                } else {
+                    vec![Difficulty::from_name(&name).ok_or_else(|| format!("unknown difficulty '{}'", name))?]
+                };
+            }
+            "--strategy" => {
+                let names = value(&arg)?;
+                strategies = if names.eq_ignore_ascii_case("all") {
+                    StrategyKind::ALL.to_vec()
+                } else {
+                    names
+                        .split(',')
+                        .map(|name| StrategyKind::from_name(name.trim()).ok_or_else(|| format!("unknown strategy '{}'", name)))
+                        .collect::<Result<_, _>>()?
+                };
+            }
+            "-c" | "--config" => config_paths.push(value(&arg)?),
+            "--catalog" => catalog_path = Some(value(&arg)?),
+            "-f" | "--format" => {
+                format = match value(&arg)?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("unknown format '{}' (expected text, json or csv)", other)),
+                }
+            }
+            "-j" | "--threads" => {
+                threads = parse_number(&value(&arg)?, &arg)?;
+                if threads == 0 {
+                    return Err(format!("{} must be at least 1", arg));
+                }
+            }
+            "--games-csv" => games_csv = Some(value(&arg)?),
+            "--assert-win-rate" => {
+                let (min, max) = parse_range(&value(&arg)?, &arg)?;
+                if min > max {
+                    return Err(format!("{} band {}..{} is empty", arg, min, max));
+                }
+                win_rate_band = Some((min, max));
+            }
+            "--tune" => {
+                let knob = value(&arg)?;
+                let (name, range) = knob
+                    .split_once('=')
+                    .ok_or_else(|| format!("{} expects PARAM=MIN..MAX, got '{}'", arg, knob))?;
+                let parameter = TuneParameter::from_name(name).ok_or_else(|| format!("can't tune '{}'", name))?;
+                let (min, max) = parse_range(range, &arg)?;
+                if min > max {
+                    return Err(format!("{} range {}..{} is empty", arg, min, max));
+                }
+                tune.push(TuneBounds { parameter, min, max });
+            }
+            "--target" => {
+                let (min, max) = parse_range(&value(&arg)?, &arg)?;
+                if min > max {
+                    return Err(format!("{} band {}..{} is empty", arg, min, max));
+                }
+                target = (min, max);
+            }
+            "-o" | "--output" => output = value(&arg)?,
+            "--solve" => solve = true,
+            "--goal" => {
+                let name = value(&arg)?;
+                goal = SolveGoal::from_name(&name).ok_or_else(|| format!("unknown goal '{}'", name))?;
+            }
+            "--nodes" => {
+                nodes = parse_number(&value(&arg)?, &arg)?;
+                if nodes == 0 {
+                    return Err(format!("{} must be at least 1", arg));
+                }
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    if games == 0 {
+        return Err("--games must be at least 1".to_string());
+    }
+    let seeds = match seed_range {
+        Some(seeds) => seeds,
+        None => {
+            let end = seed
+                .checked_add(games)
+                .ok_or_else(|| format!("--seed {} with --games {} runs past the last seed ({})", seed, games, u64::MAX))?;
+            seed..end
+        }
+    };
+
+    let mut configs = Vec::new();
+    for path in config_paths {
+        let config = GameConfig::load(&path).map_err(|err| format!("{}: {}", path, err))?;
+        configs.push((path, Arc::new(config)));
+    }
+    if configs.is_empty() {
+        configs.push(("built-in config".to_string(), Arc::new(GameConfig::builtin())));
+    }
+    let catalog = match catalog_path {
+        Some(path) => Arc::new(ResourceCatalog::load(&path).map_err(|err| format!("{}: {}", path, err))?),
+        None => ResourceCatalog::builtin_shared(),
+    };
+    if !tune.is_empty() && (configs.len() > 1 || strategies.len() > 1) {
+        return Err("--tune works from one config with one strategy".to_string());
+    }
+    for bounds in &tune {
+        for &difficulty in &difficulties {
+            bounds.validate(&configs[0].1, difficulty).map_err(|err| {
+                format!("--tune {}={}..{} on {}: {}", bounds.parameter.name(), bounds.min, bounds.max, difficulty.name(), err)
+            })?;
+        }
+    }
+    if solve && (!tune.is_empty() || configs.len() > 1) {
+        return Err("--solve works from one config, without --tune".to_string());
+    }
+
+    Ok(Some(Options {
+        seeds,
+        difficulties,
+        strategies,
+        configs,
+        catalog,
+        format,
+        threads,
+        games_csv,
+        win_rate_band,
+        tune,
+        target,
+        output,
+        solve,
+        goal,
+        nodes,
+    }))
+}
+
+// Tunes every selected preset in turn, logging each evaluation, then writes the tuned config
+fn tune(options: &Options) -> ExitCode {
+    let (name, config) = &options.configs[0];
+    let tuner = Tuner {
+        strategy: options.strategies[0],
+        catalog: options.catalog.clone(),
+        seeds: options.seeds.clone(),
+        threads: options.threads,
+        target: options.target,
+        bounds: options.tune.clone(),
+        max_rounds: TUNE_ROUNDS,
+        bisection_steps: TUNE_BISECTION_STEPS,
+    };
+
+    match options.format {
+        Format::Text => println!(
+            "\n🔧 Tuning {} with the {} bot on seeds {}..{}, targeting {:.0}-{:.0}%",
+            name,
+            tuner.strategy.name(),
+            tuner.seeds.start,
+            tuner.seeds.end,
+            tuner.target.0,
+            tuner.target.1
+        ),
+        Format::Csv => println!("difficulty,round,parameter,value,budget,burn_coefficient,move_cost,resource_density,win_rate"),
+        Format::Json => {}
+    }
+
+    let mut tuned = GameConfig::clone(config);
+    let mut results = Vec::new();
+    let mut steps = Vec::new();
+    for &difficulty in &options.difficulties {
+        if options.format == Format::Text {
+            println!("\n{}:", difficulty.name());
+        }
+        let result = tuner.tune(&tuned, difficulty, |step| {
+            log_tune_step(options.format, step);
+            steps.push(*step);
+        });
+        if options.format == Format::Text {
+            println!(
+                "  {} {:.1}% after {} runs",
+                if result.converged { "✅" } else { "❌" },
+                result.win_rate,
+                result.evaluations
+            );
+        }
+        *tuned.settings_mut(difficulty) = result.settings;
+        results.push(result);
+    }
+
+    // Read the file back through the normal loader, so a tuned config that the game would
+    // reject never gets written
+    let contents = format!(
+        "// Tuned by `simulate --tune` from {} with the {} bot on seeds {}..{}, targeting {:.0}-{:.0}%\n{}\n",
+        name,
+        tuner.strategy.name(),
+        tuner.seeds.start,
+        tuner.seeds.end,
+        tuner.target.0,
+        tuner.target.1,
+        tuned.to_ron()
+    );
+    if let Err(err) = GameConfig::from_ron(&contents) {
+        eprintln!("simulate: tuned config is unusable: {}", err);
+        return ExitCode::from(2);
+    }
+    if let Err(err) = std::fs::write(&options.output, contents) {
+        eprintln!("simulate: could not write {}: {}", options.output, err);
+        return ExitCode::from(2);
+    }
+
+    match options.format {
+        Format::Text => println!("\n💾 Tuned config written to {}", options.output),
+        Format::Json => print_tune_json(options, &results, &steps),
+        Format::Csv => {}
+    }
+    if results.iter().all(|result| result.converged) {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("❌ Some presets didn't reach {:.0}-{:.0}% within the bounds given", options.target.0, options.target.1);
+        ExitCode::FAILURE
+    }
+}
+
+// Solves every selected seed on every selected preset, one line per seed
+fn solve(options: &Options) -> ExitCode {
+    let (name, config) = &options.configs[0];
+    let solver = Solver {
+        goal: options.goal,
+        node_limit: options.nodes,
+    };
+    let single_seed = options.seeds.end - options.seeds.start == 1;
+
+    match options.format {
+        Format::Text => println!("\n🧭 Par for {} ({} plan, up to {} states per seed)", name, solver.goal.name(), solver.node_limit),
+        Format::Csv => println!("seed,difficulty,goal,won,victory,sprints,budget,fixes,waited_sprints,nodes,optimal"),
+        Format::Json => {}
+    }
+
+    let mut solutions = Vec::new();
+    for &difficulty in &options.difficulties {
+        if options.format == Format::Text {
+            println!("\n{}:", difficulty.name());
+        }
+        let settings = config.settings(difficulty);
+        for seed in options.seeds.clone() {
+            let data = GameData::with_config(difficulty, settings.grid_width, settings.grid_height, config.clone());
+            let solution = solver.solve(&GameEngine::with_catalog(data, GameRng::new(seed), options.catalog.clone()));
+            match options.format {
+                Format::Text => print_solution(&solution, single_seed),
+                Format::Csv => println!(
+                    "{},{},{},{},{},{},{},{},{},{},{}",
+                    solution.seed,
+                    difficulty.name(),
+                    solution.goal.name(),
+                    solution.won,
+                    solution.victory.map(|v| format!("{:?}", v)).unwrap_or_default(),
+                    solution.sprints,
+                    solution.budget,
+                    solution.plan.len(),
+                    solution.waited_sprints,
+                    solution.nodes,
+                    solution.optimal
+                ),
+                Format::Json => {}
+            }
+            solutions.push(solution);
+        }
+    }
+
+    if options.format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&solutions).expect("solutions serialize to JSON"));
+    }
+    if options.format == Format::Text && !single_seed {
+        let won = solutions.iter().filter(|solution| solution.won).count();
+        println!("\n  Winnable: {} of {} seeds ({:.1}%)", won, solutions.len(), won as f32 / solutions.len() as f32 * 100.0);
+    }
+    ExitCode::SUCCESS
+}
+
+fn print_solution(solution: &Solution, with_plan: bool) {
+    let searched = if solution.optimal { "" } else { ", search cut short" };
+    match solution.victory {
+        Some(victory) => println!(
+            "  Seed {:>6}: won in {} sprints with ${} ({}), {} fixes, {} states{}",
+            solution.seed,
+            solution.sprints,
+            solution.budget,
+            victory.name(),
+            solution.plan.len(),
+            solution.nodes,
+            searched
+        ),
+        None if solution.optimal => println!("  Seed {:>6}: no winning plan, {} states", solution.seed, solution.nodes),
+        None => println!("  Seed {:>6}: no win found, {} states{}", solution.seed, solution.nodes, searched),
+    }
+    if !with_plan {
+        return;
+    }
+    for (step, fix) in solution.plan.iter().enumerate() {
+        let route: Vec<String> = fix.route.iter().map(|heading| format!("{:?}", heading)).collect();
+        let tries = if fix.attempts > 1 { format!(" in {} attempts", fix.attempts) } else { String::new() };
+        println!(
+            "    {:>2}. Sail {}, fix {} at ({}, {}){}: done by sprint {}, ${} left",
+            step + 1,
+            if route.is_empty() { "nowhere".to_string() } else { route.join(" ") },
+            fix.resource,
+            fix.x,
+            fix.y,
+            tries,
+            fix.sprint,
+            fix.budget
+        );
+    }
+    if solution.waited_sprints > 0 {
+        println!("    Then wait {} sprints", solution.waited_sprints);
+    }
+}
+
+// One line of the convergence log
+fn log_tune_step(format: Format, step: &TuneStep) {
+    let settings = &step.settings;
+    match format {
+        Format::Text => println!(
+            "  round {} {:<17} {:>8}  → budget {:>6}, burn {:.3}, move {:>3}, density {:.3}: {:>5.1}%",
+            step.round,
+            step.parameter.name(),
+            format!("{:.3}", step.value).trim_end_matches('0').trim_end_matches('.'),
+            settings.budget,
+            settings.burn_coefficient,
+            settings.move_cost,
+            settings.resource_density,
+            step.win_rate
+        ),
+        Format::Csv => println!(
+            "{},{},{},{},{},{},{},{},{:.2}",
+            step.difficulty.name(),
+            step.round,
+            step.parameter.name(),
+            step.value,
+            settings.budget,
+            settings.burn_coefficient,
+            settings.move_cost,
+            settings.resource_density,
+            step.win_rate
+        ),
+        Format::Json => {}
+    }
+}
+
+#[derive(Serialize)]
+struct JsonTuning<'a> {
+    output: &'a str,
+    target: (f32, f32),
+    results: &'a [TuneResult],
+    log: &'a [TuneStep],
+}
+
+fn print_tune_json(options: &Options, results: &[TuneResult], steps: &[TuneStep]) {
+    let tuning = JsonTuning {
+        output: &options.output,
+        target: options.target,
+        results,
+        log: steps,
+    };
+    println!("{}", serde_json::to_string_pretty(&tuning).expect("tuning serializes to JSON"));
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, flag: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} expects a number, got '{}'", flag, value))
+}
+
+// "FROM..TO", used for seed ranges and win rate bands
+fn parse_range<T: std::str::FromStr>(value: &str, flag: &str) -> Result<(T, T), String> {
+    let (from, to) = value
+        .split_once("..")
+        .ok_or_else(|| format!("{} expects FROM..TO, got '{}'", flag, value))?;
+    Ok((parse_number(from, flag)?, parse_number(to, flag)?))
+}
+
+fn print_text(options: &Options, runs: &[Run]) {
+    println!("\n🏴‍☠️ Pirate FinOps Balance Simulation");
+    for (name, _) in &options.configs {
+        let config_runs: Vec<&Run> = runs.iter().filter(|run| &run.config == name).collect();
+        if options.configs.len() > 1 {
+            println!("\n⚙️ Config: {}", name);
+            println!("=============================");
+        }
+
+        // Several bots get a head-to-head per difficulty instead of a full report each
+        if options.strategies.len() > 1 {
+            for run in &config_runs {
+                print_head_to_head(&run.matchup);
+            }
+            continue;
+        }
+
+        for run in &config_runs {
+            run.matchup.reports[0].print();
+        }
+        if config_runs.len() > 1 {
+            print_difficulty_summary(config_runs.iter().map(|run| &run.matchup.reports[0]));
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRun<'a> {
+    config: &'a str,
+    report: &'a SimulationReport,
+}
+
+fn print_json(runs: &[Run]) {
+    let reports: Vec<JsonRun> = runs
+        .iter()
+        .flat_map(|run| run.matchup.reports.iter().map(|report| JsonRun { config: &run.config, report }))
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&reports).expect("reports serialize to JSON"));
+}
+
+fn print_csv(runs: &[Run]) {
+    println!("config,{}", SimulationReport::CSV_HEADER);
+    for run in runs {
+        for report in &run.matchup.reports {
+            println!("{},{}", csv_field(&run.config), report.csv_row());
+        }
+    }
+}
+
+fn create_games_csv(path: &str) -> std::io::Result<BufWriter<File>> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "config,{}", GameResult::CSV_HEADER)?;
+    Ok(file)
+}
+
+// Config names are file paths, which may contain commas or quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn seeds_past_u64_max_are_rejected() {
+        let last = u64::MAX.to_string();
+        assert!(parse(&["-s", &last, "-n", "2"]).is_err());
+
+        let options = parse(&["-s", &(u64::MAX - 2).to_string(), "-n", "2"]).unwrap().unwrap();
+        assert_eq!(options.seeds, u64::MAX - 2..u64::MAX);
+    }
+
+    #[test]
+    fn tune_bounds_the_config_would_reject_are_rejected() {
+        assert!(parse(&["--tune", "resource_density=0..2"]).is_err());
+        assert!(parse(&["--tune", "budget=-500..5000"]).is_err());
+        assert!(parse(&["--tune", "budget=5000..15000"]).is_ok());
+    }
+}
@@ -0,0 +1,512 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use crate::components::ResourceType;
+
+// Data-driven resource catalog. Every kind of waste (costs, fix times, sprites, relations
+// and spawn weights) lives in assets/data/resources.catalog.ron so trainers can add or
+// retune resources without touching Rust. `ResourceType` is an index into this catalog.
+
+pub const CATALOG_PATH: &str = "data/resources.catalog.ron";
+
+// Embedded copy so the simulator and a missing asset still have a catalog to play with
+const BUILTIN_CATALOG: &str = include_str!("../assets/data/resources.catalog.ron");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum ResourceCategory {
+    Critical,  // >$200/mo
+    High,  // $100-200/mo
+    Medium,  // $50-100/mo
+    Low,  // <$50/mo
+}
+
+impl ResourceCategory {
+    pub const ALL: [ResourceCategory; 4] = [Self::Critical, Self::High, Self::Medium, Self::Low];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+// How unfixed and fixed resources sway the waste of the resources around them.
+// Each neighbor within `radius` multiplies a resource's waste by `unfixed` (or `fixed`
+// once it is fixed); the effect weakens by `falloff` per extra tile of distance and is
+// scaled per category pair, and again by `related` for related kinds.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Neighborhood {
+    pub radius: u8,
+    pub diagonals: bool,  // Count diagonal tiles (square kernel) or cardinal distance only (diamond)
+    pub falloff: f32,
+    pub unfixed: f32,
+    pub fixed: f32,
+    pub related: f32,
+    pub category_pairs: Vec<(ResourceCategory, ResourceCategory, f32)>,  // Symmetric, 1.0 if left out
+}
+
+impl Default for Neighborhood {
+    fn default() -> Self {
+        Self {
+            radius: 1,
+            diagonals: false,
+            falloff: 1.0,
+            unfixed: 1.1,
+            fixed: 0.9,
+            related: 1.0,
+            category_pairs: Vec::new(),
+        }
+    }
+}
+
+// A tile offset inside the neighborhood kernel and how strongly it counts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KernelCell {
+    pub dx: i32,
+    pub dy: i32,
+    pub weight: f32,
+}
+
+// How a resource's waste grows while it stays unfixed, one step per interval
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub enum DecayCurve {
+    Flat,
+    Linear(f32),  // +x per step
+    Compounding(f32),  // ×x per step
+    Capped { rate: f32, max: f32 },  // Compounding, but never above `max`
+    Step { after: u32, level: f32 },  // Flat, then jumps to `level` once `after` steps have passed
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Decay {
+    pub curve: DecayCurve,
+    pub interval: u32,  // Sprints per step
+}
+
+impl Default for Decay {
+    fn default() -> Self {
+        Self {
+            curve: DecayCurve::Compounding(1.2),
+            interval: 5,
+        }
+    }
+}
+
+impl Decay {
+    // Waste multiplier once `sprint` sprints have passed (1.0 = no decay)
+    pub fn level(&self, sprint: u32) -> f32 {
+        let steps = sprint / self.interval;
+        match self.curve {
+            DecayCurve::Flat => 1.0,
+            DecayCurve::Linear(step) => 1.0 + step * steps as f32,
+            DecayCurve::Compounding(rate) => rate.powi(steps as i32),
+            DecayCurve::Capped { rate, max } => rate.powi(steps as i32).min(max),
+            DecayCurve::Step { after, level } => if steps >= after { level } else { 1.0 },
+        }
+    }
+
+    // The lowest level from `sprint` on. Curves that shrink waste have no floor above zero.
+    pub fn lowest_from(&self, sprint: u32) -> f32 {
+        match self.curve {
+            DecayCurve::Flat => 1.0,
+            DecayCurve::Linear(step) if step >= 0.0 => self.level(sprint),
+            DecayCurve::Compounding(rate) | DecayCurve::Capped { rate, .. } if rate >= 1.0 => self.level(sprint),
+            DecayCurve::Step { after, level } if sprint / self.interval < after => level.min(1.0),
+            DecayCurve::Step { level, .. } => level,
+            _ => 0.0,
+        }
+    }
+
+    // First sprint after `sprint` at which the level changes, if it ever does
+    pub fn next_growth(&self, sprint: u32) -> Option<u32> {
+        let steps = sprint / self.interval;
+        let next_step = match self.curve {
+            DecayCurve::Flat => return None,
+            DecayCurve::Linear(_) | DecayCurve::Compounding(_) => steps + 1,
+            DecayCurve::Capped { max, .. } => {
+                if self.level(sprint) >= max {
+                    return None;
+                }
+                steps + 1
+            }
+            DecayCurve::Step { after, .. } => {
+                if steps >= after {
+                    return None;
+                }
+                after
+            }
+        };
+        Some(next_step * self.interval)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ResourceKind {
+    pub id: String,
+    pub name: String,
+    pub category: ResourceCategory,
+    pub waste_cost: i32,
+    pub fix_sprints: u8,
+    pub savings_range: (i32, i32),
+    pub hint: String,
+    pub icon: String,
+    pub color: (f32, f32, f32),
+    pub sprite: String,
+    pub spawn_weight: u32,
+    // Chance that a completed fix fails and the sprints spent on it are wasted
+    #[serde(default)]
+    pub fix_failure_chance: f32,
+    // One-off budget credit returned when the fix completes
+    #[serde(default)]
+    pub bonus_credit: i32,
+    // Fixing it uncovers any hidden resource nearby, not just related ones
+    #[serde(default)]
+    pub reveals_owner: bool,
+    // How fast its waste grows while unfixed (compounding ×1.2 every 5 sprints if left out)
+    #[serde(default)]
+    pub decay: Decay,
+}
+
+impl ResourceKind {
+    fn empty() -> Self {
+        Self {
+            id: "Empty".to_string(),
+            name: "Clear Waters".to_string(),
+            category: ResourceCategory::Low,
+            waste_cost: 0,
+            fix_sprints: 0,
+            savings_range: (0, 0),
+            hint: "Clear waters".to_string(),
+            icon: "✨".to_string(),
+            color: (0.6, 0.6, 0.6),
+            sprite: String::new(),
+            spawn_weight: 0,
+            fix_failure_chance: 0.0,
+            bonus_credit: 0,
+            reveals_owner: false,
+            decay: Decay {
+                curve: DecayCurve::Flat,
+                interval: 1,
+            },
+        }
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.category == ResourceCategory::Critical
+    }
+
+    pub fn icon_color(&self) -> Color {
+        Color::srgb(self.color.0, self.color.1, self.color.2)
+    }
+}
+
+// On-disk layout of a catalog file
+#[derive(Deserialize)]
+struct CatalogFile {
+    resources: Vec<ResourceKind>,
+    relations: Vec<(String, String)>,
+    #[serde(default)]
+    neighborhood: Neighborhood,
+}
+
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct ResourceCatalog {
+    kinds: Vec<ResourceKind>,  // Index 0 is always clear water
+    related: Vec<Vec<bool>>,
+    neighborhood: Neighborhood,
+    kernel: Vec<KernelCell>,
+    pair_scale: [[f32; 4]; 4],
+}
+
+impl ResourceCatalog {
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_CATALOG).expect("built-in resource catalog is valid")
+    }
+
+    // The built-in catalog, parsed once and shared by every engine that doesn't bring its own
+    pub fn builtin_shared() -> Arc<Self> {
+        static BUILTIN: OnceLock<Arc<ResourceCatalog>> = OnceLock::new();
+        BUILTIN.get_or_init(|| Arc::new(Self::builtin())).clone()
+    }
+
+    // Reads a catalog file from disk, for tools like the simulator that run without the asset server
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let source = std::fs::read_to_string(path).map_err(CatalogError::Io)?;
+        Self::from_ron(&source)
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, CatalogError> {
+        let file: CatalogFile = ron::from_str(source).map_err(CatalogError::Parse)?;
+        Self::from_file(file)
+    }
+
+    fn from_file(file: CatalogFile) -> Result<Self, CatalogError> {
+        if file.resources.is_empty() {
+            return Err(CatalogError::invalid("catalog has no resources"));
+        }
+        if file.resources.len() >= u16::MAX as usize {
+            return Err(CatalogError::invalid("catalog has too many resources"));
+        }
+
+        let mut kinds = vec![ResourceKind::empty()];
+        for kind in file.resources {
+            if kinds.iter().any(|k| k.id == kind.id) {
+                return Err(CatalogError::invalid(format!("duplicate resource id `{}`", kind.id)));
+            }
+            if kind.fix_sprints == 0 {
+                return Err(CatalogError::invalid(format!("`{}` needs at least one fix sprint", kind.id)));
+            }
+            // Negative costs or savings would turn into negative waste and burn
+            if kind.waste_cost < 0 || kind.savings_range.0 < 0 {
+                return Err(CatalogError::invalid(format!("`{}` waste_cost and savings_range can't be negative", kind.id)));
+            }
+            if kind.savings_range.0 > kind.savings_range.1 {
+                return Err(CatalogError::invalid(format!("`{}` savings_range is reversed", kind.id)));
+            }
+            if !(0.0..=1.0).contains(&kind.fix_failure_chance) {
+                return Err(CatalogError::invalid(format!("`{}` fix_failure_chance must be between 0 and 1", kind.id)));
+            }
+            if kind.decay.interval == 0 {
+                return Err(CatalogError::invalid(format!("`{}` decay interval must be at least one sprint", kind.id)));
+            }
+            kinds.push(kind);
+        }
+        if kinds.iter().all(|k| k.spawn_weight == 0) {
+            return Err(CatalogError::invalid("at least one resource needs a spawn_weight"));
+        }
+
+        let neighborhood = file.neighborhood;
+        if neighborhood.unfixed <= 0.0 || neighborhood.fixed <= 0.0 {
+            return Err(CatalogError::invalid("neighborhood multipliers must be positive"));
+        }
+        if neighborhood.falloff < 0.0 || neighborhood.related < 0.0 || neighborhood.category_pairs.iter().any(|&(_, _, scale)| scale < 0.0) {
+            return Err(CatalogError::invalid("neighborhood falloff, related and category pair scales can't be negative"));
+        }
+        let mut pair_scale = [[1.0; 4]; 4];
+        for &(a, b, scale) in &neighborhood.category_pairs {
+            pair_scale[a.index()][b.index()] = scale;
+            pair_scale[b.index()][a.index()] = scale;
+        }
+
+        let mut catalog = Self {
+            related: vec![vec![false; kinds.len()]; kinds.len()],
+            kinds,
+            kernel: kernel_cells(&neighborhood),
+            neighborhood,
+            pair_scale,
+        };
+        for (a, b) in &file.relations {
+            let (Some(a), Some(b)) = (catalog.find(a), catalog.find(b)) else {
+                return Err(CatalogError::invalid(format!("relation ({}, {}) names an unknown resource", a, b)));
+            };
+            catalog.related[a.0 as usize][b.0 as usize] = true;
+            catalog.related[b.0 as usize][a.0 as usize] = true;
+        }
+        Ok(catalog)
+    }
+
+    pub fn kind(&self, resource: ResourceType) -> &ResourceKind {
+        &self.kinds[resource.0 as usize]
+    }
+
+    pub fn find(&self, id: &str) -> Option<ResourceType> {
+        self.kinds
+            .iter()
+            .position(|k| k.id == id)
+            .map(|i| ResourceType(i as u16))
+    }
+
+    // Every real resource kind, without clear water
+    pub fn resource_types(&self) -> impl Iterator<Item = ResourceType> {
+        (1..self.kinds.len() as u16).map(ResourceType)
+    }
+
+    // Related resources chain combos (e.g. a VM and its disks)
+    pub fn are_related(&self, a: ResourceType, b: ResourceType) -> bool {
+        self.related[a.0 as usize][b.0 as usize]
+    }
+
+    pub fn neighborhood(&self) -> &Neighborhood {
+        &self.neighborhood
+    }
+
+    // Every offset the neighborhood reaches, with its falloff weight
+    pub fn kernel(&self) -> &[KernelCell] {
+        &self.kernel
+    }
+
+    // Factor a neighbor applies to a resource's waste: `weight` comes from the kernel
+    pub fn neighbor_factor(&self, resource: ResourceType, neighbor: ResourceType, neighbor_fixed: bool, weight: f32) -> f32 {
+        let base = if neighbor_fixed { self.neighborhood.fixed } else { self.neighborhood.unfixed };
+        let mut strength = weight * self.pair_scale[self.kind(resource).category.index()][self.kind(neighbor).category.index()];
+        if self.are_related(resource, neighbor) {
+            strength *= self.neighborhood.related;
+        }
+        1.0 + (base - 1.0) * strength
+    }
+
+    // Whether both catalogs define the same kinds in the same order, so a grid built
+    // with one can be read with the other
+    pub fn same_kinds(&self, other: &ResourceCatalog) -> bool {
+        self.kinds.len() == other.kinds.len()
+            && self.kinds.iter().zip(&other.kinds).all(|(a, b)| a.id == b.id)
+    }
+
+    // Picks a resource with probability proportional to its spawn weight
+    pub fn random_resource(&self, rng: &mut impl Rng) -> ResourceType {
+        let total: u32 = self.kinds.iter().map(|k| k.spawn_weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for (i, kind) in self.kinds.iter().enumerate() {
+            if roll < kind.spawn_weight {
+                return ResourceType(i as u16);
+            }
+            roll -= kind.spawn_weight;
+        }
+        unreachable!("roll is below the total spawn weight")
+    }
+}
+
+fn kernel_cells(neighborhood: &Neighborhood) -> Vec<KernelCell> {
+    let radius = neighborhood.radius as i32;
+    let mut cells = Vec::new();
+    for dx in -radius..=radius {
+        for dy in -radius..=radius {
+            let distance = if neighborhood.diagonals {
+                dx.abs().max(dy.abs())
+            } else {
+                dx.abs() + dy.abs()
+            };
+            if distance == 0 || distance > radius {
+                continue;
+            }
+            cells.push(KernelCell {
+                dx,
+                dy,
+                weight: neighborhood.falloff.powi(distance - 1),
+            });
+        }
+    }
+    cells
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl CatalogError {
+    fn invalid(message: impl Into<String>) -> Self {
+        Self::Invalid(message.into())
+    }
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read resource catalog: {}", err),
+            Self::Parse(err) => write!(f, "could not parse resource catalog: {}", err),
+            Self::Invalid(message) => write!(f, "invalid resource catalog: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[derive(Default)]
+pub struct ResourceCatalogLoader;
+
+impl AssetLoader for ResourceCatalogLoader {
+    type Asset = ResourceCatalog;
+    type Settings = ();
+    type Error = CatalogError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<ResourceCatalog, CatalogError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await.map_err(CatalogError::Io)?;
+        ResourceCatalog::from_ron(&source)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["catalog.ron"]
+    }
+}
+
+// Handle to the catalog asset, kept alive so edits to the file are hot-reloaded
+#[derive(Resource)]
+pub struct ResourceCatalogHandle(pub Handle<ResourceCatalog>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A one-resource catalog with `fields` spliced into the resource and `neighborhood` as given
+    fn catalog(fields: &str, neighborhood: &str) -> Result<ResourceCatalog, CatalogError> {
+        ResourceCatalog::from_ron(&format!(
+            r#"(
+                resources: [
+                    (
+                        id: "Disk",
+                        name: "Disk",
+                        category: Low,
+                        fix_sprints: 1,
+                        hint: "",
+                        icon: "",
+                        color: (1.0, 1.0, 1.0),
+                        sprite: "",
+                        spawn_weight: 1,
+                        {}
+                    ),
+                ],
+                relations: [],
+                neighborhood: ({}),
+            )"#,
+            fields, neighborhood
+        ))
+    }
+
+    fn rejects(fields: &str, neighborhood: &str) -> bool {
+        matches!(catalog(fields, neighborhood), Err(CatalogError::Invalid(_)))
+    }
+
+    #[test]
+    fn shipped_catalog_parses() {
+        let catalog = ResourceCatalog::from_ron(BUILTIN_CATALOG).unwrap();
+        assert!(catalog.resource_types().count() > 0);
+        assert!(catalog.find("IdleVM").is_some());
+        assert_eq!(catalog.kind(ResourceType::EMPTY).waste_cost, 0);
+    }
+
+    #[test]
+    fn accepts_a_minimal_catalog() {
+        let catalog = catalog("waste_cost: 30, savings_range: (35, 45),", "").unwrap();
+        assert_eq!(catalog.resource_types().count(), 1);
+    }
+
+    #[test]
+    fn rejects_negative_costs_and_savings() {
+        assert!(rejects("waste_cost: -30, savings_range: (35, 45),", ""));
+        assert!(rejects("waste_cost: 30, savings_range: (-45, -35),", ""));
+        assert!(rejects("waste_cost: 30, savings_range: (45, 35),", ""));
+    }
+
+    #[test]
+    fn rejects_negative_neighborhood_scales() {
+        let fields = "waste_cost: 30, savings_range: (35, 45),";
+        assert!(rejects(fields, "falloff: -0.5"));
+        assert!(rejects(fields, "related: -1.0"));
+        assert!(rejects(fields, "category_pairs: [(Low, Critical, -1.0)]"));
+        assert!(rejects(fields, "unfixed: 0.0"));
+    }
+}
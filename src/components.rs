@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use bevy::math::Quat;
 use serde::{Deserialize, Serialize};
+use crate::engine::Heading;
+use crate::resources::Difficulty;
+use crate::solver::{Solution, SolveRun};
+use std::sync::{Arc, Mutex};
 
 #[derive(Component)]
 pub struct Tile {
@@ -8,11 +12,29 @@ pub struct Tile {
     pub y: u8,
 }
 
+// Ocean, grid overlay and grid lines drawn under the tiles
+#[derive(Component)]
+pub struct MapBackground;
+
 #[derive(Component)]
 pub struct TileIcon {
     pub parent: Entity,
 }
 
+// Debug overlay label with a tile's effective neighborhood multiplier
+#[derive(Component)]
+pub struct NeighborhoodLabel {
+    pub x: u8,
+    pub y: u8,
+}
+
+// Decay level and sprints until it next grows, drawn on a revealed resource tile
+#[derive(Component)]
+pub struct DecayLabel {
+    pub x: u8,
+    pub y: u8,
+}
+
 #[derive(Component)]
 pub struct Player {
     pub x: u8,
@@ -22,6 +44,13 @@ pub struct Player {
 #[derive(Component)]
 pub struct PlayerMarker;
 
+// The main 2D camera. It follows the ship, offset by how far the player has dragged it.
+#[derive(Component, Default)]
+pub struct GameCamera {
+    pub pan: Vec2,
+    pub last_cursor: Option<Vec2>,  // Cursor position while a drag is in progress
+}
+
 #[derive(Component)]
 pub struct RotationAnimation {
     pub start_rotation: Quat,
@@ -29,254 +58,69 @@ pub struct RotationAnimation {
     pub timer: Timer,
 }
 
-#[derive(Component, Default)]
+#[derive(Component, Clone, Copy, Debug, Default)]
 pub struct TileState {
     pub revealed: bool,
     pub fixed: bool,
     pub fixing_sprints_left: u8,
     pub decay_multiplier: f32,  // 1.0 = no decay, 1.2 = 20% worse, etc.
+    pub outage_sprints_left: u8,  // Cascading outage from a failed neighbor fix
+    pub hidden: bool,  // Resource not visible until a related upstream resource is fixed
+    pub savings_estimate: i32,  // Midpoint of the savings range (with combo) when the fix landed
+    pub savings_actual: i32,  // Savings the fix really delivered, rolled inside the range
 }
 
-#[derive(Component, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
-pub enum ResourceType {
-    // High Value (Critical)
-    OverprovisionedOpenShift,
-    MissedReservation,
-    IdleGPUCluster,
-
-    // Medium-High Value
-    IdleVM,
-    OversizedAppService,
-    UnusedSQLDatabase,
-    OverprovisionedCosmosDB,
-
-    // Medium Value
-    LogIngestionBloat,
-    ExpiredBackups,
-    RedundantLoadBalancer,
-
-    // Low Value
-    OrphanedDisk,
-    StaleSnapshot,
-    UnusedPublicIP,
-    UntaggedResource,
-    IdleCDNEndpoint,
-    EmptyStorageAccount,
-
-    // Special
-    Empty,
-}
+// Index of a resource kind in the `ResourceCatalog`; 0 is clear water
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResourceType(pub u16);
 
 impl ResourceType {
-    pub fn waste_cost(&self) -> i32 {
-        match self {
-            // Critical (>$200/mo)
-            Self::OverprovisionedOpenShift => 320,
-            Self::MissedReservation => 280,
-            Self::IdleGPUCluster => 450,
-
-            // High ($100-200/mo)
-            Self::IdleVM => 150,
-            Self::OversizedAppService => 120,
-            Self::UnusedSQLDatabase => 180,
-            Self::OverprovisionedCosmosDB => 160,
-
-            // Medium ($50-100/mo)
-            Self::LogIngestionBloat => 80,
-            Self::ExpiredBackups => 60,
-            Self::RedundantLoadBalancer => 90,
-
-            // Low (<$50/mo)
-            Self::OrphanedDisk => 30,
-            Self::StaleSnapshot => 25,
-            Self::UnusedPublicIP => 20,
-            Self::UntaggedResource => 10,
-            Self::IdleCDNEndpoint => 35,
-            Self::EmptyStorageAccount => 15,
-
-            Self::Empty => 0,
-        }
-    }
+    pub const EMPTY: ResourceType = ResourceType(0);
 
-    pub fn fix_sprints(&self) -> u8 {
-        match self {
-            // Complex fixes (3 sprints)
-            Self::OverprovisionedOpenShift => 3,
-            Self::IdleGPUCluster => 3,
-            Self::OverprovisionedCosmosDB => 3,
-
-            // Moderate fixes (2 sprints)
-            Self::IdleVM => 2,
-            Self::MissedReservation => 2,
-            Self::OversizedAppService => 2,
-            Self::UnusedSQLDatabase => 2,
-            Self::LogIngestionBloat => 2,
-            Self::RedundantLoadBalancer => 2,
-
-            // Quick fixes (1 sprint)
-            Self::OrphanedDisk => 1,
-            Self::StaleSnapshot => 1,
-            Self::UnusedPublicIP => 1,
-            Self::UntaggedResource => 1,
-            Self::ExpiredBackups => 1,
-            Self::IdleCDNEndpoint => 1,
-            Self::EmptyStorageAccount => 1,
-
-            Self::Empty => 0,
-        }
-    }
-
-    pub fn savings_range(&self) -> (i32, i32) {
-        match self {
-            // Excellent ROI
-            Self::IdleGPUCluster => (600, 750),
-            Self::OverprovisionedOpenShift => (420, 520),
-            Self::MissedReservation => (380, 480),
-
-            // Good ROI
-            Self::UnusedSQLDatabase => (240, 300),
-            Self::OverprovisionedCosmosDB => (210, 280),
-            Self::IdleVM => (200, 250),
-            Self::OversizedAppService => (160, 200),
-
-            // Moderate ROI
-            Self::RedundantLoadBalancer => (110, 140),
-            Self::LogIngestionBloat => (100, 130),
-            Self::ExpiredBackups => (75, 95),
-
-            // Low ROI
-            Self::IdleCDNEndpoint => (40, 50),
-            Self::OrphanedDisk => (35, 45),
-            Self::StaleSnapshot => (28, 35),
-            Self::UnusedPublicIP => (22, 28),
-            Self::EmptyStorageAccount => (16, 20),
-            Self::UntaggedResource => (10, 12),
-
-            Self::Empty => (0, 0),
-        }
+    pub fn is_empty(&self) -> bool {
+        *self == Self::EMPTY
     }
+}
 
-    pub fn icon(&self) -> &str {
-        match self {
-            Self::IdleGPUCluster => "🎮",
-            Self::OverprovisionedOpenShift => "🐙",
-            Self::MissedReservation => "🧭",
-            Self::IdleVM => "💻",
-            Self::UnusedSQLDatabase => "🗄️",
-            Self::OverprovisionedCosmosDB => "🌌",
-            Self::OversizedAppService => "🧱",
-            Self::LogIngestionBloat => "📝",
-            Self::ExpiredBackups => "📦",
-            Self::RedundantLoadBalancer => "⚖️",
-            Self::OrphanedDisk => "💾",
-            Self::StaleSnapshot => "📸",
-            Self::UnusedPublicIP => "🌐",
-            Self::UntaggedResource => "🏷️",
-            Self::IdleCDNEndpoint => "📡",
-            Self::EmptyStorageAccount => "📂",
-            Self::Empty => "✨",
-        }
-    }
+// What the sea looks like on a tile. Terrain is charted, so it's visible through the fog.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Terrain {
+    #[default]
+    OpenWater,
+    Reef,  // Blocks movement
+    Island,  // Blocks movement
+    Storm,  // Sailing in costs double
+    Current(Heading),  // Pushes the ship one more tile in its direction
+}
 
-    pub fn hint(&self) -> &str {
-        match self {
-            // Critical
-            Self::IdleGPUCluster => "GPU cluster idle - $450/mo CRITICAL!",
-            Self::OverprovisionedOpenShift => "OpenShift overscaled - $320/mo waste!",
-            Self::MissedReservation => "No reservation - $280/mo overpay!",
-
-            // High
-            Self::UnusedSQLDatabase => "SQL Database unused - $180/mo waste",
-            Self::OverprovisionedCosmosDB => "Cosmos DB oversized - $160/mo waste",
-            Self::IdleVM => "VM running idle - $150/mo waste",
-            Self::OversizedAppService => "App Service oversized - $120/mo",
-
-            // Medium
-            Self::RedundantLoadBalancer => "Duplicate load balancer - $90/mo",
-            Self::LogIngestionBloat => "Log ingestion high - $80/mo",
-            Self::ExpiredBackups => "Old backups retained - $60/mo",
-
-            // Low
-            Self::IdleCDNEndpoint => "CDN endpoint unused - $35/mo",
-            Self::OrphanedDisk => "Orphaned disk - $30/mo",
-            Self::StaleSnapshot => "Stale snapshot - $25/mo",
-            Self::UnusedPublicIP => "Unused public IP - $20/mo",
-            Self::EmptyStorageAccount => "Empty storage - $15/mo",
-            Self::UntaggedResource => "Missing tags - $10/mo",
-
-            Self::Empty => "Clear waters",
-        }
+impl Terrain {
+    pub fn is_passable(&self) -> bool {
+        !matches!(self, Self::Reef | Self::Island)
     }
 
-    pub fn get_azure_icon(&self) -> &str {
+    pub fn cost_multiplier(&self) -> i32 {
         match self {
-            Self::IdleGPUCluster => "🎮",
-            Self::OverprovisionedOpenShift => "⚙️",
-            Self::MissedReservation => "📆",
-            Self::IdleVM => "☁️",
-            Self::UnusedSQLDatabase => "🗄️",
-            Self::OverprovisionedCosmosDB => "🌌",
-            Self::OversizedAppService => "🏭",
-            Self::LogIngestionBloat => "📈",
-            Self::ExpiredBackups => "📦",
-            Self::RedundantLoadBalancer => "⚖️",
-            Self::OrphanedDisk => "💾",
-            Self::StaleSnapshot => "📷",
-            Self::UnusedPublicIP => "🌐",
-            Self::UntaggedResource => "🏷️",
-            Self::IdleCDNEndpoint => "📡",
-            Self::EmptyStorageAccount => "📂",
-            Self::Empty => "✨",
+            Self::Storm => 2,
+            _ => 1,
         }
     }
 
-    pub fn get_icon_color(&self) -> bevy::prelude::Color {
-        use bevy::prelude::Color;
+    pub fn name(&self) -> &str {
         match self {
-            Self::IdleGPUCluster => Color::srgb(1.0, 0.2, 0.2),
-            Self::OverprovisionedOpenShift => Color::srgb(0.2, 0.6, 1.0),
-            Self::MissedReservation => Color::srgb(1.0, 0.5, 0.2),
-            Self::IdleVM => Color::srgb(0.5, 0.7, 1.0),
-            Self::UnusedSQLDatabase => Color::srgb(0.8, 0.4, 0.8),
-            Self::OverprovisionedCosmosDB => Color::srgb(0.6, 0.3, 0.9),
-            Self::OversizedAppService => Color::srgb(0.4, 0.4, 0.7),
-            Self::LogIngestionBloat => Color::srgb(0.9, 0.3, 0.3),
-            Self::ExpiredBackups => Color::srgb(0.7, 0.5, 0.3),
-            Self::RedundantLoadBalancer => Color::srgb(0.5, 0.8, 0.5),
-            Self::OrphanedDisk => Color::srgb(0.6, 0.6, 0.9),
-            Self::StaleSnapshot => Color::srgb(0.7, 0.5, 0.9),
-            Self::UnusedPublicIP => Color::srgb(0.3, 0.9, 0.3),
-            Self::UntaggedResource => Color::srgb(0.9, 0.9, 0.3),
-            Self::IdleCDNEndpoint => Color::srgb(0.4, 0.7, 0.7),
-            Self::EmptyStorageAccount => Color::srgb(0.8, 0.8, 0.5),
-            Self::Empty => Color::srgb(0.6, 0.6, 0.6),
+            Self::OpenWater => "Open water",
+            Self::Reef => "Reef",
+            Self::Island => "Island",
+            Self::Storm => "Storm cell",
+            Self::Current(_) => "Current",
         }
     }
 
     pub fn get_sprite_path(&self) -> &str {
         match self {
-            // Use different patterns for each resource
-            Self::IdleGPUCluster => "sprites/patterns/pattern_0029.png",  // Special pattern for GPU
-            Self::OverprovisionedOpenShift => "sprites/patterns/pattern_0005.png",
-            Self::MissedReservation => "sprites/patterns/pattern_0013.png",
-
-            Self::IdleVM => "sprites/patterns/pattern_0001.png",
-            Self::UnusedSQLDatabase => "sprites/patterns/pattern_0019.png",
-            Self::OverprovisionedCosmosDB => "sprites/patterns/pattern_0021.png",
-            Self::OversizedAppService => "sprites/patterns/pattern_0017.png",
-
-            Self::LogIngestionBloat => "sprites/patterns/pattern_0015.png",
-            Self::ExpiredBackups => "sprites/patterns/pattern_0023.png",
-            Self::RedundantLoadBalancer => "sprites/patterns/pattern_0025.png",
-
-            Self::OrphanedDisk => "sprites/patterns/pattern_0003.png",
-            Self::StaleSnapshot => "sprites/patterns/pattern_0011.png",
-            Self::UnusedPublicIP => "sprites/patterns/pattern_0009.png",
-            Self::UntaggedResource => "sprites/patterns/pattern_0007.png",
-            Self::IdleCDNEndpoint => "sprites/patterns/pattern_0027.png",
-            Self::EmptyStorageAccount => "sprites/patterns/pattern_0002.png",
-
-            Self::Empty => "",
+            Self::OpenWater => "",
+            Self::Reef => "sprites/tile_50.png",
+            Self::Island => "sprites/tile_71.png",
+            Self::Storm | Self::Current(_) => "sprites/tile_73.png",
         }
     }
 }
@@ -316,6 +160,26 @@ pub struct ComboText;
 #[derive(Component)]
 pub struct CurrentTileText;
 
+#[derive(Component)]
+pub struct RouteText;
+
+#[derive(Component)]
+pub struct ForecastText;
+
+#[derive(Component)]
+pub struct SeedText;
+
+#[derive(Component)]
+pub struct DifficultyButton {
+    pub difficulty: Difficulty,
+}
+
+#[derive(Component)]
+pub struct DifficultyText;
+
+#[derive(Component)]
+pub struct MapSizeText;
+
 #[derive(Component)]
 pub struct ActionButton {
     pub action: GameAction,
@@ -330,6 +194,16 @@ pub enum GameAction {
 #[derive(Component)]
 pub struct GameOverUI;
 
+// Game-over line that shows par once its search is done
+#[derive(Component)]
+pub struct ParText(pub ParSearch);
+
+// A background task fills the slot; where there's no thread to spare, the run goes a slice a frame
+pub enum ParSearch {
+    Background(Arc<Mutex<Option<Solution>>>),
+    Sliced(Box<SolveRun>),
+}
+
 #[derive(Component)]
 pub struct RevealAnimation {
     pub timer: Timer,
@@ -338,4 +212,9 @@ pub struct RevealAnimation {
 #[derive(Component)]
 pub struct FixAnimation {
     pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct FloatingText {
+    pub timer: Timer,
 }
\ No newline at end of file
@@ -0,0 +1,306 @@
+use std::fmt;
+use std::path::Path;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::resources::{Difficulty, DifficultySettings, MAX_GRID_SIZE, MIN_GRID_SIZE};
+use crate::win_conditions::{VictorySet, VictoryThresholds};
+
+// Every economic constant in one place: burn, calendar, combos, risks and the difficulty
+// presets. The game loads assets/data/game.config.ron (hot-reloaded in debug builds) and the
+// simulator can load alternative files side by side for A/B comparisons. Anything left out
+// of a file keeps the default below.
+
+pub const CONFIG_PATH: &str = "data/game.config.ron";
+
+// Embedded copy so the simulator and a missing asset still have the shipped tuning
+const BUILTIN_CONFIG: &str = include_str!("../assets/data/game.config.ron");
+
+#[derive(Asset, TypePath, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub base_burn: i32,  // Flat burn every sprint, on top of the waste burn
+    pub sprints_per_month: u32,
+    pub realization_lag_sprints: u32,  // Sprints before a fix's savings reach the bill
+    pub combo_step: f32,  // Combo gained per chained related fix
+    pub combo_cap: f32,
+    pub adjacency_bonus: i32,  // Per already-fixed cardinal neighbor of a completed fix
+    pub cascade_chance: f32,  // Chance a failed critical fix knocks over its neighbors
+    pub outage_sprints: u8,
+    pub outage_multiplier: f32,
+    pub hidden_chance: f32,  // Chance a downstream resource near its upstream starts hidden
+    pub hidden_link_radius: i32,
+    pub min_sprints_to_win: u32,  // Win conditions are only checked from this sprint on
+    pub victory: VictoryThresholds,
+    pub difficulties: DifficultyPresets,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DifficultyPresets {
+    pub easy: DifficultySettings,
+    pub normal: DifficultySettings,
+    pub hard: DifficultySettings,
+    pub nightmare: DifficultySettings,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            base_burn: 30,
+            sprints_per_month: 2,
+            realization_lag_sprints: 4,
+            combo_step: 0.2,
+            combo_cap: 2.0,
+            adjacency_bonus: 50,
+            cascade_chance: 0.5,
+            outage_sprints: 3,
+            outage_multiplier: 1.5,
+            hidden_chance: 0.35,
+            hidden_link_radius: 2,
+            min_sprints_to_win: 5,
+            victory: VictoryThresholds::default(),
+            difficulties: DifficultyPresets::default(),
+        }
+    }
+}
+
+impl Default for DifficultyPresets {
+    fn default() -> Self {
+        Self {
+            easy: DifficultySettings {
+                budget: 11500,
+                grid_width: 8,
+                grid_height: 8,
+                resource_density: 0.40,
+                move_cost: 45,
+                burn_coefficient: 0.38,
+                decay_scale: 0.9,
+                terrain_density: 0.10,
+                win_conditions: VictorySet::all(),
+            },
+            normal: DifficultySettings {
+                budget: 10500,
+                grid_width: 8,
+                grid_height: 8,
+                resource_density: 0.40,
+                move_cost: 50,
+                burn_coefficient: 0.4,
+                decay_scale: 1.0,
+                terrain_density: 0.15,
+                win_conditions: VictorySet::all(),
+            },
+            hard: DifficultySettings {
+                budget: 10500,
+                grid_width: 8,
+                grid_height: 8,
+                resource_density: 0.42,
+                move_cost: 55,
+                burn_coefficient: 0.4,
+                decay_scale: 1.1,
+                terrain_density: 0.20,
+                win_conditions: VictorySet::all(),
+            },
+            // A bigger estate, so the war chest is bigger too
+            nightmare: DifficultySettings {
+                budget: 13000,
+                grid_width: 9,
+                grid_height: 9,
+                resource_density: 0.40,
+                move_cost: 60,
+                burn_coefficient: 0.42,
+                decay_scale: 1.1,
+                terrain_density: 0.25,
+                win_conditions: VictorySet::all(),
+            },
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_CONFIG).expect("built-in game config is valid")
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, ConfigError> {
+        let config: GameConfig = ron::from_str(source).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    // Reads a config file from disk, for tools like the simulator that run without the asset server
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let source = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::from_ron(&source)
+    }
+
+    pub fn settings(&self, difficulty: Difficulty) -> DifficultySettings {
+        match difficulty {
+            Difficulty::Easy => self.difficulties.easy,
+            Difficulty::Normal => self.difficulties.normal,
+            Difficulty::Hard => self.difficulties.hard,
+            Difficulty::Nightmare => self.difficulties.nightmare,
+        }
+    }
+
+    pub fn settings_mut(&mut self, difficulty: Difficulty) -> &mut DifficultySettings {
+        match difficulty {
+            Difficulty::Easy => &mut self.difficulties.easy,
+            Difficulty::Normal => &mut self.difficulties.normal,
+            Difficulty::Hard => &mut self.difficulties.hard,
+            Difficulty::Nightmare => &mut self.difficulties.nightmare,
+        }
+    }
+
+    // Pretty RON that `from_ron` reads back, for tools that write tuned configs
+    pub fn to_ron(&self) -> String {
+        let pretty = ron::ser::PrettyConfig::new().struct_names(false);
+        ron::ser::to_string_pretty(self, pretty).expect("game config serializes to RON")
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.base_burn < 0 || self.adjacency_bonus < 0 {
+            return Err(ConfigError::invalid("base_burn and adjacency_bonus can't be negative"));
+        }
+        if self.sprints_per_month == 0 {
+            return Err(ConfigError::invalid("sprints_per_month must be at least 1"));
+        }
+        if self.combo_step < 0.0 || self.combo_cap < 1.0 {
+            return Err(ConfigError::invalid("combo_step can't be negative and combo_cap must be at least 1.0"));
+        }
+        if !(0.0..=1.0).contains(&self.cascade_chance) || !(0.0..=1.0).contains(&self.hidden_chance) {
+            return Err(ConfigError::invalid("cascade_chance and hidden_chance must be between 0 and 1"));
+        }
+        if self.outage_multiplier <= 0.0 || self.hidden_link_radius < 0 {
+            return Err(ConfigError::invalid("outage_multiplier must be positive and hidden_link_radius can't be negative"));
+        }
+
+        let victory = &self.victory;
+        if victory.elimination_max_waste < 0 || victory.combo_max_waste < 0 || victory.combo_target < 1.0 {
+            return Err(ConfigError::invalid("victory waste bars can't be negative and combo_target must be at least 1.0"));
+        }
+        // The combo never climbs past the cap, so Combo Master could never be won
+        if victory.combo_target > self.combo_cap {
+            return Err(ConfigError::invalid("victory combo_target can't be above combo_cap"));
+        }
+        if victory.savings_max_waste_percentage < 0.0 || victory.balanced_max_waste_percentage < 0.0 {
+            return Err(ConfigError::invalid("victory waste percentages can't be negative"));
+        }
+
+        for difficulty in Difficulty::ALL {
+            let settings = self.settings(difficulty);
+            let name = difficulty.name();
+            if settings.budget <= 0 {
+                return Err(ConfigError::invalid(format!("{} budget must be positive", name)));
+            }
+            let grid_sizes = MIN_GRID_SIZE..=MAX_GRID_SIZE;
+            if !grid_sizes.contains(&settings.grid_width) || !grid_sizes.contains(&settings.grid_height) {
+                return Err(ConfigError::invalid(format!(
+                    "{} grid must be {}-{} tiles on each side",
+                    name, MIN_GRID_SIZE, MAX_GRID_SIZE
+                )));
+            }
+            if !(0.0..=1.0).contains(&settings.resource_density) || !(0.0..=1.0).contains(&settings.terrain_density) {
+                return Err(ConfigError::invalid(format!("{} densities must be between 0 and 1", name)));
+            }
+            if settings.move_cost < 0 || settings.burn_coefficient < 0.0 || settings.decay_scale < 0.0 {
+                return Err(ConfigError::invalid(format!(
+                    "{} move_cost, burn_coefficient and decay_scale can't be negative",
+                    name
+                )));
+            }
+            if settings.win_conditions.is_empty() {
+                return Err(ConfigError::invalid(format!("{} must enable at least one win condition", name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl ConfigError {
+    fn invalid(message: impl Into<String>) -> Self {
+        Self::Invalid(message.into())
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read game config: {}", err),
+            Self::Parse(err) => write!(f, "could not parse game config: {}", err),
+            Self::Invalid(message) => write!(f, "invalid game config: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Default)]
+pub struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = ConfigError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<GameConfig, ConfigError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await.map_err(ConfigError::Io)?;
+        GameConfig::from_ron(&source)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+// Handle to the config asset, kept alive so edits to the file are hot-reloaded
+#[derive(Resource)]
+pub struct GameConfigHandle(pub Handle<GameConfig>);
+
+// The loaded config, or the built-in one while the file is loading or failed to parse
+pub fn current_config(configs: &Assets<GameConfig>, handle: &GameConfigHandle) -> GameConfig {
+    configs.get(&handle.0).cloned().unwrap_or_else(GameConfig::builtin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::win_conditions::VictoryType;
+
+    #[test]
+    fn defaults_match_the_shipped_file() {
+        assert_eq!(GameConfig::default(), GameConfig::builtin());
+    }
+
+    #[test]
+    fn to_ron_reads_back_unchanged() {
+        let mut config = GameConfig::builtin();
+        config.base_burn = 45;
+        config.difficulties.hard.burn_coefficient = 0.425;
+        config.difficulties.easy.win_conditions = VictorySet::NONE.with(VictoryType::Savings);
+        assert_eq!(GameConfig::from_ron(&config.to_ron()).unwrap(), config);
+    }
+
+    #[test]
+    fn combo_target_above_the_cap_is_rejected() {
+        let mut config = GameConfig::builtin();
+        config.victory.combo_target = config.combo_cap + 0.1;
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+        config.victory.combo_target = config.combo_cap;
+        assert!(config.validate().is_ok());
+    }
+}
@@ -0,0 +1,1054 @@
+use std::sync::Arc;
+use bevy::prelude::Resource;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::catalog::ResourceCatalog;
+use crate::components::{ResourceType, Terrain, TileState};
+use crate::navigation;
+use crate::config::GameConfig;
+use crate::resources::{GameData, GameRng, LossCause, generate_random_grid, generate_terrain};
+use crate::win_conditions::{VictoryType, WinConditions};
+
+// Headless rules engine - the single source of truth for game rules.
+// The Bevy systems and the simulator both drive the game through `GameEngine::apply`.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Heading {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Heading {
+    pub const ALL: [Heading; 4] = [Self::North, Self::South, Self::East, Self::West];
+
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Self::North => (0, 1),
+            Self::South => (0, -1),
+            Self::East => (1, 0),
+            Self::West => (-1, 0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Move(Heading),
+    Fix,
+    AdvanceSprint,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectReason {
+    GameOver,
+    OutOfBounds,
+    Blocked,
+    NotRevealed,
+    NothingToFix,
+    AlreadyFixed,
+    FixInProgress,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    Moved { x: u8, y: u8, heading: Heading, cost: i32 },
+    Drifted { x: u8, y: u8 },
+    Revealed { x: u8, y: u8, resource: ResourceType },
+    FixStarted { x: u8, y: u8, resource: ResourceType, sprints: u8 },
+    FixCompleted { x: u8, y: u8, resource: ResourceType, savings: i32, combo: f32 },
+    FixFailed { x: u8, y: u8, resource: ResourceType },
+    CascadeOutage { x: u8, y: u8, resource: ResourceType },
+    HiddenWasteUncovered { x: u8, y: u8, resource: ResourceType, waste: i32 },
+    BonusCredit { x: u8, y: u8, resource: ResourceType, amount: i32 },
+    MonthClosed { month: u32, credited: i32 },
+    SprintProcessed { sprint: u32, burn: i32 },
+    DecayApplied { sprint: u32 },
+    Rejected(RejectReason),
+    GameWon(VictoryType),
+    GameLost,
+}
+
+// Whether fixing `upstream` uncovers a hidden `downstream` resource. Tagging an untagged
+// resource reveals whoever really owns it; otherwise the link follows related resources,
+// with the pricier resource hiding the cheaper leftovers (a VM hides its disks and snapshots).
+pub fn uncovers(catalog: &ResourceCatalog, upstream: ResourceType, downstream: ResourceType) -> bool {
+    if upstream.is_empty() || downstream.is_empty() {
+        return false;
+    }
+    let (up, down) = (catalog.kind(upstream), catalog.kind(downstream));
+    if up.reveals_owner {
+        return !down.reveals_owner;
+    }
+    catalog.are_related(upstream, downstream) && up.waste_cost > down.waste_cost
+}
+
+// What sailing a route would cost and uncover, worked out without touching the game
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoutePreview {
+    pub waypoints: Vec<(u8, u8)>,  // Every tile the ship passes through, starting where it is now
+    pub cost: i32,
+    pub uncovered: Vec<(u8, u8)>,  // Hidden tiles the scan arc will reveal along the way
+}
+
+// Where the numbers would land once a fix completes (assuming it succeeds), worked out
+// without touching the game
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixProjection {
+    pub resource: ResourceType,
+    pub sprints: u8,
+    pub failure_chance: f32,
+    pub burn_rate: i32,
+    pub waste_percentage: f32,
+    pub monthly_savings: i32,
+    pub combo_multiplier: f32,
+}
+
+// Monthly savings from a completed fix, credited at month closes from `from_sprint` on
+#[derive(Clone)]
+struct RealizingSavings {
+    from_sprint: u32,
+    amount: i32,
+}
+
+// Combo, adjacency bonus and estimated savings earned by completing a fix
+struct FixRewards {
+    combo_multiplier: f32,
+    adjacency_bonus: i32,
+    estimated_savings: i32,
+}
+
+#[derive(Resource)]
+pub struct GameEngine {
+    data: GameData,
+    catalog: Arc<ResourceCatalog>,
+    grid: Vec<Vec<ResourceType>>,
+    terrain: Vec<Vec<Terrain>>,
+    tiles: Vec<Vec<TileState>>,
+    neighborhood: Vec<Vec<f32>>,  // Cached neighborhood multiplier of every tile
+    realizing: Vec<RealizingSavings>,
+    heading: Heading,
+    rng: GameRng,
+    win_conditions: WinConditions,
+}
+
+// Win conditions are boxed, so a copy rebuilds them from the same settings and config
+impl Clone for GameEngine {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            catalog: self.catalog.clone(),
+            grid: self.grid.clone(),
+            terrain: self.terrain.clone(),
+            tiles: self.tiles.clone(),
+            neighborhood: self.neighborhood.clone(),
+            realizing: self.realizing.clone(),
+            heading: self.heading,
+            rng: self.rng.clone(),
+            win_conditions: WinConditions::for_game(&self.data),
+        }
+    }
+}
+
+impl GameEngine {
+    // Generates the map from the seeded RNG; the engine keeps drawing from it for in-game rolls
+    pub fn new(data: GameData, rng: GameRng) -> Self {
+        Self::with_catalog(data, rng, ResourceCatalog::builtin_shared())
+    }
+
+    pub fn with_catalog(data: GameData, mut rng: GameRng, catalog: Arc<ResourceCatalog>) -> Self {
+        let grid = generate_random_grid(
+            data.grid_width,
+            data.grid_height,
+            data.settings.resource_density,
+            &catalog,
+            &mut rng,
+        );
+        let start = (data.player_x, data.player_y);
+        let terrain = generate_terrain(&grid, start, data.settings.terrain_density, &mut rng);
+        Self::with_grid(data, grid, terrain, rng, catalog)
+    }
+
+    pub fn with_grid(
+        data: GameData,
+        grid: Vec<Vec<ResourceType>>,
+        terrain: Vec<Vec<Terrain>>,
+        rng: GameRng,
+        catalog: Arc<ResourceCatalog>,
+    ) -> Self {
+        let (width, height) = (data.grid_width as usize, data.grid_height as usize);
+        let tile = TileState {
+            decay_multiplier: 1.0,
+            ..Default::default()
+        };
+
+        let win_conditions = WinConditions::for_game(&data);
+        let mut engine = Self {
+            data,
+            catalog,
+            grid,
+            terrain,
+            tiles: vec![vec![tile; height]; width],
+            neighborhood: vec![vec![1.0; height]; width],
+            realizing: Vec::new(),
+            // Ship starts facing south, so the first scan covers the tiles below it
+            heading: Heading::South,
+            rng,
+            win_conditions,
+        };
+
+        engine.conceal_downstream_resources();
+        engine.reveal_scan_area();
+        engine.neighborhood = engine.neighborhood_grid(&engine.tiles);
+        engine.recalculate_waste();
+        engine
+    }
+
+    // The same map back at its first sprint, rebuilt from the seed
+    pub fn restart(&self) -> GameEngine {
+        let data = GameData::with_config(self.data.difficulty, self.grid_width(), self.grid_height(), self.data.config.clone());
+        Self::with_catalog(data, GameRng::new(self.seed()), self.catalog.clone())
+    }
+
+    // The game as a player might picture it: every resource they can't see is swapped for
+    // `grid`'s, which of those are hidden waste is rolled afresh, and future rolls come from
+    // `rng`. Bots search copies like this without peeking at the real map or the real dice.
+    pub fn determinize(&self, grid: &[Vec<ResourceType>], rng: GameRng) -> GameEngine {
+        let mut engine = self.clone();
+        engine.rng = rng;
+
+        let unseen: Vec<(u8, u8)> = (0..self.grid_width())
+            .flat_map(|x| (0..self.grid_height()).map(move |y| (x, y)))
+            .filter(|&(x, y)| !self.tile(x, y).revealed || self.tile(x, y).hidden)
+            .collect();
+        for &(x, y) in &unseen {
+            engine.grid[x as usize][y as usize] = grid[x as usize][y as usize];
+        }
+        for &(x, y) in &unseen {
+            // A revealed tile looks like clear water, so anything there must be hidden
+            let hidden = if self.tile(x, y).revealed {
+                !engine.resource_at(x, y).is_empty()
+            } else {
+                engine.rolls_hidden(x, y)
+            };
+            let tile = &mut engine.tiles[x as usize][y as usize];
+            tile.hidden = hidden;
+            // Decay and outages belong to the real resource, so the imagined one starts afresh
+            tile.decay_multiplier = 1.0;
+            tile.outage_sprints_left = 0;
+        }
+
+        // Brought up to date before the waste, which the next sprint's burn is charged from
+        engine.apply_decay();
+        engine.neighborhood = engine.neighborhood_grid(&engine.tiles);
+        engine.recalculate_waste();
+        engine
+    }
+
+    pub fn data(&self) -> &GameData {
+        &self.data
+    }
+
+    pub fn catalog(&self) -> &ResourceCatalog {
+        &self.catalog
+    }
+
+    // Hot-swaps a retuned catalog mid-game. Only works when the resource kinds are unchanged,
+    // since the grid stores catalog indices.
+    pub fn replace_catalog(&mut self, catalog: Arc<ResourceCatalog>) -> bool {
+        if !self.catalog.same_kinds(&catalog) {
+            return false;
+        }
+        self.catalog = catalog;
+        self.neighborhood = self.neighborhood_grid(&self.tiles);
+        self.apply_decay();
+        self.recalculate_waste();
+        true
+    }
+
+    // Hot-swaps retuned economics mid-game. The map and starting budget stay as generated.
+    pub fn replace_config(&mut self, config: Arc<GameConfig>) {
+        self.data.settings = config.settings(self.data.difficulty);
+        self.data.config = config;
+        self.win_conditions = WinConditions::for_game(&self.data);
+        self.apply_decay();
+        self.recalculate_waste();
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn grid_width(&self) -> u8 {
+        self.data.grid_width
+    }
+
+    pub fn grid_height(&self) -> u8 {
+        self.data.grid_height
+    }
+
+    pub fn heading(&self) -> Heading {
+        self.heading
+    }
+
+    pub fn resource_at(&self, x: u8, y: u8) -> ResourceType {
+        self.grid[x as usize][y as usize]
+    }
+
+    // What the player can see on a tile: hidden resources look like clear water
+    pub fn visible_resource_at(&self, x: u8, y: u8) -> ResourceType {
+        if self.tile(x, y).hidden {
+            ResourceType::EMPTY
+        } else {
+            self.resource_at(x, y)
+        }
+    }
+
+    pub fn terrain_at(&self, x: u8, y: u8) -> Terrain {
+        self.terrain[x as usize][y as usize]
+    }
+
+    pub fn terrain(&self) -> &[Vec<Terrain>] {
+        &self.terrain
+    }
+
+    // Cheapest route from the ship to (x, y) around reefs and islands
+    pub fn find_path(&self, x: u8, y: u8) -> Option<Vec<Heading>> {
+        let from = (self.data.player_x, self.data.player_y);
+        navigation::find_path(&self.terrain, self.data.settings.move_cost, from, (x, y))
+    }
+
+    // Cheapest cost of sailing from the ship to every tile, None where it can't get to
+    pub fn sailing_costs(&self) -> Vec<Vec<Option<i32>>> {
+        let from = (self.data.player_x, self.data.player_y);
+        navigation::sailing_costs(&self.terrain, self.data.settings.move_cost, from)
+    }
+
+    // Dry run of sailing `route` from the ship's position, stopping early if a step is blocked
+    pub fn preview_route(&self, route: &[Heading]) -> RoutePreview {
+        let (mut x, mut y) = (self.data.player_x, self.data.player_y);
+        let mut preview = RoutePreview {
+            waypoints: vec![(x, y)],
+            ..Default::default()
+        };
+
+        let scan = |x: u8, y: u8, heading: Heading, uncovered: &mut Vec<(u8, u8)>| {
+            for (sx, sy) in self.scan_arc(x, y, heading) {
+                let tile = (sx as u8, sy as u8);
+                if !self.tile(tile.0, tile.1).revealed && !uncovered.contains(&tile) {
+                    uncovered.push(tile);
+                }
+            }
+        };
+
+        for &heading in route {
+            let Some(step) = navigation::step(&self.terrain, x, y, heading) else {
+                break;
+            };
+            preview.cost += self.data.settings.move_cost * step.cost_multiplier;
+            preview.waypoints.push(step.entered);
+            scan(step.entered.0, step.entered.1, heading, &mut preview.uncovered);
+
+            if step.landed != step.entered {
+                preview.waypoints.push(step.landed);
+                scan(step.landed.0, step.landed.1, heading, &mut preview.uncovered);
+            }
+            (x, y) = step.landed;
+        }
+        preview
+    }
+
+    // What fixing the tile under the ship would do to burn, waste and savings,
+    // including the neighborhood and combo effects. Rejected like `Action::Fix` would be.
+    pub fn evaluate_fix(&self) -> Result<FixProjection, RejectReason> {
+        let (x, y) = (self.data.player_x, self.data.player_y);
+        let resource = self.fixable_resource(x, y)?;
+        let rewards = self.fix_rewards(x, y);
+
+        let mut tiles = self.tiles.clone();
+        tiles[x as usize][y as usize].fixed = true;
+        let mut neighborhood = self.neighborhood.clone();
+        for ((nx, ny), multiplier) in self.neighborhood_around(&tiles, x, y) {
+            neighborhood[nx as usize][ny as usize] = multiplier;
+        }
+
+        let mut data = self.data.clone();
+        data.total_waste = self.total_waste(&tiles, &neighborhood);
+        data.monthly_savings += rewards.estimated_savings;
+
+        let kind = self.catalog.kind(resource);
+        Ok(FixProjection {
+            resource,
+            sprints: kind.fix_sprints,
+            failure_chance: kind.fix_failure_chance,
+            burn_rate: data.calculate_burn_rate(),
+            waste_percentage: data.waste_percentage(),
+            monthly_savings: data.monthly_savings,
+            combo_multiplier: rewards.combo_multiplier,
+        })
+    }
+
+    // Waste multiplier of a resource after `sprint` sprints unfixed, with the difficulty's scaling
+    pub fn decay_level(&self, resource: ResourceType, sprint: u32) -> f32 {
+        let level = self.catalog.kind(resource).decay.level(sprint);
+        1.0 + (level - 1.0) * self.data.settings.decay_scale
+    }
+
+    // The lowest `decay_level` the resource can be at from `sprint` on
+    pub fn decay_floor(&self, resource: ResourceType, sprint: u32) -> f32 {
+        let level = self.catalog.kind(resource).decay.lowest_from(sprint);
+        (1.0 + (level - 1.0) * self.data.settings.decay_scale).max(0.0)
+    }
+
+    // Sprint at which the resource at (x, y) next decays, if it is unfixed and still growing
+    pub fn next_decay_at(&self, x: u8, y: u8) -> Option<u32> {
+        let resource = self.resource_at(x, y);
+        if resource.is_empty() || self.tile(x, y).fixed {
+            return None;
+        }
+        self.catalog.kind(resource).decay.next_growth(self.data.sprint)
+    }
+
+    // Earliest sprint at which any unfixed resource decays
+    pub fn next_decay_sprint(&self) -> Option<u32> {
+        (0..self.grid_width())
+            .flat_map(|x| (0..self.grid_height()).map(move |y| (x, y)))
+            .filter_map(|(x, y)| self.next_decay_at(x, y))
+            .min()
+    }
+
+    // Combined pull of the neighbors on the waste at (x, y): above 1.0 amplifies, below dampens
+    pub fn neighborhood_multiplier(&self, x: u8, y: u8) -> f32 {
+        self.neighborhood[x as usize][y as usize]
+    }
+
+    pub fn tile(&self, x: u8, y: u8) -> &TileState {
+        &self.tiles[x as usize][y as usize]
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.data.game_won || self.data.game_lost
+    }
+
+    pub fn apply(&mut self, action: Action) -> Vec<GameEvent> {
+        if self.is_over() {
+            return vec![GameEvent::Rejected(RejectReason::GameOver)];
+        }
+
+        let mut events = match action {
+            Action::Move(heading) => self.move_player(heading),
+            Action::Fix => self.start_fix(),
+            Action::AdvanceSprint => self.advance_sprint(),
+        };
+
+        self.check_game_over(&mut events);
+        events
+    }
+
+    fn move_player(&mut self, heading: Heading) -> Vec<GameEvent> {
+        let (x, y) = (self.data.player_x, self.data.player_y);
+        let (dx, dy) = heading.delta();
+        if !self.in_bounds(x as i32 + dx, y as i32 + dy) {
+            return vec![GameEvent::Rejected(RejectReason::OutOfBounds)];
+        }
+        let Some(step) = navigation::step(&self.terrain, x, y, heading) else {
+            return vec![GameEvent::Rejected(RejectReason::Blocked)];
+        };
+
+        (self.data.player_x, self.data.player_y) = step.entered;
+        self.heading = heading;
+
+        // Storm cells cost more to sail through
+        let cost = self.data.settings.move_cost * step.cost_multiplier;
+        self.data.budget -= cost;
+        if self.data.budget <= 0 {
+            self.data.game_lost = true;
+            self.data.loss = Some(LossCause::Sailing);
+        }
+
+        let mut events = vec![GameEvent::Moved {
+            x: self.data.player_x,
+            y: self.data.player_y,
+            heading,
+            cost,
+        }];
+        events.extend(self.reveal_scan_area());
+
+        // Currents carry the ship on for free, scanning again where it lands
+        if step.landed != step.entered {
+            (self.data.player_x, self.data.player_y) = step.landed;
+            events.push(GameEvent::Drifted { x: step.landed.0, y: step.landed.1 });
+            events.extend(self.reveal_scan_area());
+        }
+        events
+    }
+
+    fn start_fix(&mut self) -> Vec<GameEvent> {
+        let (x, y) = (self.data.player_x, self.data.player_y);
+        let resource = match self.fixable_resource(x, y) {
+            Ok(resource) => resource,
+            Err(reason) => return vec![GameEvent::Rejected(reason)],
+        };
+
+        let sprints = self.catalog.kind(resource).fix_sprints;
+        self.tiles[x as usize][y as usize].fixing_sprints_left = sprints;
+
+        // Starting a fix spends the current sprint working on it
+        let mut events = vec![GameEvent::FixStarted { x, y, resource, sprints }];
+        events.extend(self.advance_sprint());
+        events
+    }
+
+    // Brings every unfixed resource's decay level up to date. Returns whether any changed.
+    fn apply_decay(&mut self) -> bool {
+        let mut decayed = false;
+        for x in 0..self.grid.len() {
+            for y in 0..self.grid[x].len() {
+                let resource = self.grid[x][y];
+                if self.tiles[x][y].fixed || resource.is_empty() {
+                    continue;
+                }
+                let level = self.decay_level(resource, self.data.sprint);
+                let tile = &mut self.tiles[x][y];
+                if level != tile.decay_multiplier {
+                    tile.decay_multiplier = level;
+                    decayed = true;
+                }
+            }
+        }
+        decayed
+    }
+
+    // The resource a fix at (x, y) would work on, or why it can't start
+    fn fixable_resource(&self, x: u8, y: u8) -> Result<ResourceType, RejectReason> {
+        let resource = self.visible_resource_at(x, y);
+        let tile = self.tile(x, y);
+
+        if !tile.revealed {
+            return Err(RejectReason::NotRevealed);
+        }
+        if resource.is_empty() {
+            return Err(RejectReason::NothingToFix);
+        }
+        if tile.fixed {
+            return Err(RejectReason::AlreadyFixed);
+        }
+        if tile.fixing_sprints_left > 0 {
+            return Err(RejectReason::FixInProgress);
+        }
+        Ok(resource)
+    }
+
+    // One sprint passes: burn is charged, every in-progress fix ticks down together and decay is applied
+    fn advance_sprint(&mut self) -> Vec<GameEvent> {
+        let burn = self.data.calculate_burn_rate();
+        // The month closes together with its last sprint, so the credit lands before the burn can bankrupt us
+        let month_close = self.close_month(self.data.sprint + 1);
+        self.data.process_sprint();
+        let mut events = vec![GameEvent::SprintProcessed { sprint: self.data.sprint, burn }];
+        events.extend(month_close);
+
+        let mut finished = Vec::new();
+        for x in 0..self.grid.len() {
+            for y in 0..self.grid[x].len() {
+                let tile = &mut self.tiles[x][y];
+                tile.outage_sprints_left = tile.outage_sprints_left.saturating_sub(1);
+                if tile.fixing_sprints_left > 0 {
+                    tile.fixing_sprints_left -= 1;
+                    if tile.fixing_sprints_left == 0 {
+                        finished.push((x as u8, y as u8));
+                    }
+                }
+            }
+        }
+
+        for (x, y) in finished {
+            let failure_chance = self.catalog.kind(self.resource_at(x, y)).fix_failure_chance;
+            if failure_chance > 0.0 && self.rng.gen::<f32>() < failure_chance {
+                events.extend(self.fail_fix(x, y));
+            } else {
+                self.tiles[x as usize][y as usize].fixed = true;
+                events.push(self.complete_fix(x, y));
+                events.extend(self.award_bonus_credit(x, y));
+                events.extend(self.uncover_hidden_waste(x, y));
+            }
+        }
+
+        // Unfixed resources decay along their own curves
+        if self.apply_decay() {
+            events.push(GameEvent::DecayApplied { sprint: self.data.sprint });
+        }
+
+        self.recalculate_waste();
+        events
+    }
+
+    fn complete_fix(&mut self, x: u8, y: u8) -> GameEvent {
+        let resource = self.resource_at(x, y);
+        let rewards = self.fix_rewards(x, y);
+
+        // The estimate is the middle of the savings range; what the bill actually shows is rolled inside it
+        let (min_savings, max_savings) = self.catalog.kind(resource).savings_range;
+        let savings = (self.rng.gen_range(min_savings..=max_savings) as f32 * rewards.combo_multiplier) as i32;
+        let tile = &mut self.tiles[x as usize][y as usize];
+        tile.savings_estimate = rewards.estimated_savings;
+        tile.savings_actual = savings;
+
+        self.data.combo_multiplier = rewards.combo_multiplier;
+        self.data.resources_fixed_in_order.push(resource);
+        self.data.adjacency_bonus += rewards.adjacency_bonus;
+        self.data.monthly_savings += savings;
+        self.data.estimated_savings += rewards.estimated_savings;
+
+        for ((nx, ny), multiplier) in self.neighborhood_around(&self.tiles, x, y) {
+            self.neighborhood[nx as usize][ny as usize] = multiplier;
+        }
+        self.realizing.push(RealizingSavings {
+            from_sprint: self.data.sprint + self.data.config.realization_lag_sprints,
+            amount: savings,
+        });
+
+        GameEvent::FixCompleted { x, y, resource, savings, combo: rewards.combo_multiplier }
+    }
+
+    fn fix_rewards(&self, x: u8, y: u8) -> FixRewards {
+        let resource = self.resource_at(x, y);
+
+        // Combo if fixing same type or related resources
+        let mut combo_multiplier = self.data.combo_multiplier;
+        if let Some(&last_fixed) = self.data.resources_fixed_in_order.last() {
+            if resource == last_fixed || self.catalog.are_related(resource, last_fixed) {
+                combo_multiplier = (combo_multiplier + self.data.config.combo_step).min(self.data.config.combo_cap);
+            } else {
+                combo_multiplier = 1.0;
+            }
+        }
+
+        // Adjacency bonus for every already-fixed cardinal neighbor
+        let adjacent_fixed = self
+            .cardinal_neighbors(x, y)
+            .filter(|&(nx, ny)| self.tile(nx, ny).fixed)
+            .count();
+
+        // Estimated savings with combo multiplier
+        let (min_savings, max_savings) = self.catalog.kind(resource).savings_range;
+        let base_savings = (min_savings + max_savings) / 2;
+
+        FixRewards {
+            combo_multiplier,
+            adjacency_bonus: self.data.config.adjacency_bonus * adjacent_fixed as i32,
+            estimated_savings: (base_savings as f32 * combo_multiplier) as i32,
+        }
+    }
+
+    // Credits every realized fix's savings if `sprint` ends a month
+    fn close_month(&mut self, sprint: u32) -> Option<GameEvent> {
+        let sprints_per_month = self.data.config.sprints_per_month;
+        if !sprint.is_multiple_of(sprints_per_month) {
+            return None;
+        }
+        let credited: i32 = self
+            .realizing
+            .iter()
+            .filter(|savings| savings.from_sprint <= sprint)
+            .map(|savings| savings.amount)
+            .sum();
+        self.data.budget += credited;
+        self.data.realized_savings += credited;
+        Some(GameEvent::MonthClosed { month: sprint / sprints_per_month, credited })
+    }
+
+    // Refunds and credits go straight back into the budget
+    fn award_bonus_credit(&mut self, x: u8, y: u8) -> Option<GameEvent> {
+        let resource = self.resource_at(x, y);
+        let amount = self.catalog.kind(resource).bonus_credit;
+        if amount <= 0 {
+            return None;
+        }
+
+        self.data.budget += amount;
+        self.data.bonus_credits_earned += amount;
+        Some(GameEvent::BonusCredit { x, y, resource, amount })
+    }
+
+    // Hides some downstream resources near the upstream resource that would expose them
+    fn conceal_downstream_resources(&mut self) {
+        for x in 0..self.grid_width() {
+            for y in 0..self.grid_height() {
+                if self.rolls_hidden(x, y) {
+                    self.tiles[x as usize][y as usize].hidden = true;
+                }
+            }
+        }
+    }
+
+    // Whether the resource at (x, y) starts out hidden, drawing a roll only when an upstream
+    // resource nearby could expose it
+    fn rolls_hidden(&mut self, x: u8, y: u8) -> bool {
+        let resource = self.resource_at(x, y);
+        if self.catalog.kind(resource).is_critical() {
+            return false;
+        }
+
+        let has_upstream = self
+            .tiles_within(x, y, self.data.config.hidden_link_radius)
+            .any(|(ux, uy)| uncovers(&self.catalog, self.resource_at(ux, uy), resource));
+        has_upstream && self.rng.gen::<f32>() < self.data.config.hidden_chance
+    }
+
+    // Fixing an upstream resource exposes the hidden waste linked to it
+    fn uncover_hidden_waste(&mut self, x: u8, y: u8) -> Vec<GameEvent> {
+        let upstream = self.resource_at(x, y);
+        let linked: Vec<(u8, u8)> = self
+            .tiles_within(x, y, self.data.config.hidden_link_radius)
+            .filter(|&(hx, hy)| self.tile(hx, hy).hidden && uncovers(&self.catalog, upstream, self.resource_at(hx, hy)))
+            .collect();
+
+        let mut events = Vec::new();
+        for (hx, hy) in linked {
+            let resource = self.resource_at(hx, hy);
+            let tile = &mut self.tiles[hx as usize][hy as usize];
+            tile.hidden = false;
+            if !tile.revealed {
+                tile.revealed = true;
+                self.data.tiles_revealed_count += 1;
+            }
+
+            let waste = (self.catalog.kind(resource).waste_cost as f32 * tile.decay_multiplier) as i32;
+            self.data.hidden_waste_discovered += waste;
+            events.push(GameEvent::HiddenWasteUncovered { x: hx, y: hy, resource, waste });
+        }
+        events
+    }
+
+    // The sprints spent are lost; the tile stays unfixed and may take its neighbors down with it
+    fn fail_fix(&mut self, x: u8, y: u8) -> Vec<GameEvent> {
+        self.data.failed_fix_attempts += 1;
+        let mut events = vec![GameEvent::FixFailed { x, y, resource: self.resource_at(x, y) }];
+
+        if self.rng.gen::<f32>() >= self.data.config.cascade_chance {
+            return events;
+        }
+
+        let neighbors: Vec<(u8, u8)> = self.cardinal_neighbors(x, y).collect();
+        for (nx, ny) in neighbors {
+            let resource = self.resource_at(nx, ny);
+            let tile = &mut self.tiles[nx as usize][ny as usize];
+            if tile.fixed || resource.is_empty() {
+                continue;
+            }
+            tile.outage_sprints_left = self.data.config.outage_sprints;
+            self.data.cascade_failures.push((nx, ny));
+            events.push(GameEvent::CascadeOutage { x: nx, y: ny, resource });
+        }
+        events
+    }
+
+    // Reveals the tile under the ship plus the 3-tile arc ahead of it
+    fn reveal_scan_area(&mut self) -> Vec<GameEvent> {
+        let scan: Vec<_> = self.scan_arc(self.data.player_x, self.data.player_y, self.heading).collect();
+
+        let mut events = Vec::new();
+        for (x, y) in scan {
+            let tile = &mut self.tiles[x as usize][y as usize];
+            if tile.revealed {
+                continue;
+            }
+            tile.revealed = true;
+            self.data.tiles_revealed_count += 1;
+
+            let resource = self.visible_resource_at(x as u8, y as u8);
+            if self.catalog.kind(resource).is_critical() {
+                self.data.critical_resources_found += 1;
+            }
+            events.push(GameEvent::Revealed { x: x as u8, y: y as u8, resource });
+        }
+        events
+    }
+
+    // Hidden resources are still on the bill, so they count towards the waste (and the burn and
+    // win conditions that follow it); the HUD shows how much of it is undiscovered
+    fn recalculate_waste(&mut self) {
+        self.data.total_waste = self.total_waste(&self.tiles, &self.neighborhood);
+        self.data.undiscovered_waste = self.undiscovered_waste();
+    }
+
+    // Total waste from ALL unfixed resources given these tile states and neighborhood
+    // multipliers, with decay and outages
+    fn total_waste(&self, tiles: &[Vec<TileState>], neighborhood: &[Vec<f32>]) -> i32 {
+        self.tiles_with_waste(tiles).map(|(x, y)| self.waste_at(tiles, neighborhood, x, y)).sum()
+    }
+
+    // The share of the total waste sitting on hidden tiles
+    fn undiscovered_waste(&self) -> i32 {
+        self.tiles_with_waste(&self.tiles)
+            .filter(|&(x, y)| self.tile(x, y).hidden)
+            .map(|(x, y)| self.waste_at(&self.tiles, &self.neighborhood, x, y))
+            .sum()
+    }
+
+    // Every tile holding an unfixed resource
+    fn tiles_with_waste<'a>(&'a self, tiles: &'a [Vec<TileState>]) -> impl Iterator<Item = (u8, u8)> + 'a {
+        (0..self.grid_width())
+            .flat_map(move |x| (0..self.grid_height()).map(move |y| (x, y)))
+            .filter(move |&(x, y)| !tiles[x as usize][y as usize].fixed && !self.resource_at(x, y).is_empty())
+    }
+
+    fn waste_at(&self, tiles: &[Vec<TileState>], neighborhood: &[Vec<f32>], x: u8, y: u8) -> i32 {
+        let tile = &tiles[x as usize][y as usize];
+        let outage_multiplier = if tile.outage_sprints_left > 0 { self.data.config.outage_multiplier } else { 1.0 };
+
+        (self.catalog.kind(self.resource_at(x, y)).waste_cost as f32
+            * tile.decay_multiplier
+            * neighborhood[x as usize][y as usize]
+            * outage_multiplier) as i32
+    }
+
+    // Neighborhood multiplier of the resource at (x, y): every resource the catalog's kernel
+    // reaches pushes its waste up while unfixed and down once fixed
+    fn neighborhood_at(&self, tiles: &[Vec<TileState>], x: u8, y: u8) -> f32 {
+        let resource = self.resource_at(x, y);
+        if resource.is_empty() {
+            return 1.0;
+        }
+
+        self.catalog
+            .kernel()
+            .iter()
+            .filter_map(|cell| {
+                let (nx, ny) = (x as i32 + cell.dx, y as i32 + cell.dy);
+                if !self.in_bounds(nx, ny) {
+                    return None;
+                }
+                let neighbor = self.resource_at(nx as u8, ny as u8);
+                if neighbor.is_empty() {
+                    return None;
+                }
+                let fixed = tiles[nx as usize][ny as usize].fixed;
+                Some(self.catalog.neighbor_factor(resource, neighbor, fixed, cell.weight))
+            })
+            .product()
+    }
+
+    fn neighborhood_grid(&self, tiles: &[Vec<TileState>]) -> Vec<Vec<f32>> {
+        (0..self.grid_width())
+            .map(|x| (0..self.grid_height()).map(|y| self.neighborhood_at(tiles, x, y)).collect())
+            .collect()
+    }
+
+    // Fresh multipliers for every tile whose kernel reaches (x, y), after that tile changed.
+    // Kernels are symmetric, so these are the tiles (x, y)'s own kernel reaches.
+    fn neighborhood_around(&self, tiles: &[Vec<TileState>], x: u8, y: u8) -> Vec<((u8, u8), f32)> {
+        self.catalog
+            .kernel()
+            .iter()
+            .map(|cell| (x as i32 + cell.dx, y as i32 + cell.dy))
+            .filter(|&(nx, ny)| self.in_bounds(nx, ny))
+            .map(|(nx, ny)| {
+                let (nx, ny) = (nx as u8, ny as u8);
+                ((nx, ny), self.neighborhood_at(tiles, nx, ny))
+            })
+            .collect()
+    }
+
+    fn check_game_over(&mut self, events: &mut Vec<GameEvent>) {
+        if self.data.game_lost {
+            events.push(GameEvent::GameLost);
+            return;
+        }
+        if !self.data.game_won {
+            self.data.check_win_condition(&self.win_conditions);
+            if let Some(victory) = self.data.victory {
+                events.push(GameEvent::GameWon(victory));
+            }
+        }
+    }
+
+    // The tile at (x, y) plus the 3 tiles ahead of it, perpendicular to the heading
+    fn scan_arc(&self, x: u8, y: u8, heading: Heading) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let (px, py) = (x as i32, y as i32);
+        let (dx, dy) = heading.delta();
+        let (cx, cy) = (px + dx, py + dy);
+        [(px, py), (cx, cy), (cx - dy, cy - dx), (cx + dy, cy + dx)]
+            .into_iter()
+            .filter(|&(x, y)| self.in_bounds(x, y))
+    }
+
+    fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.grid_width() as i32 && y < self.grid_height() as i32
+    }
+
+    // Every other tile within `radius` (Chebyshev distance) of (x, y)
+    fn tiles_within(&self, x: u8, y: u8, radius: i32) -> impl Iterator<Item = (u8, u8)> + '_ {
+        (-radius..=radius)
+            .flat_map(move |dx| (-radius..=radius).map(move |dy| (dx, dy)))
+            .filter(|&(dx, dy)| (dx, dy) != (0, 0))
+            .map(move |(dx, dy)| (x as i32 + dx, y as i32 + dy))
+            .filter(|&(nx, ny)| self.in_bounds(nx, ny))
+            .map(|(nx, ny)| (nx as u8, ny as u8))
+    }
+
+    fn cardinal_neighbors(&self, x: u8, y: u8) -> impl Iterator<Item = (u8, u8)> + '_ {
+        Heading::ALL
+            .into_iter()
+            .map(move |d| {
+                let (dx, dy) = d.delta();
+                (x as i32 + dx, y as i32 + dy)
+            })
+            .filter(|&(nx, ny)| self.in_bounds(nx, ny))
+            .map(|(nx, ny)| (nx as u8, ny as u8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngCore;
+    use super::*;
+    use crate::resources::Difficulty;
+    use crate::win_conditions::VictorySet;
+
+    fn engine(seed: u64) -> GameEngine {
+        GameEngine::new(GameData::new(Difficulty::Normal), GameRng::new(seed))
+    }
+
+    // A fixed script that sails around, fixes whatever it lands on and lets sprints pass
+    fn play_script(engine: &mut GameEngine) {
+        let script = [
+            Action::Move(Heading::South),
+            Action::Fix,
+            Action::Move(Heading::East),
+            Action::AdvanceSprint,
+            Action::Move(Heading::North),
+            Action::Fix,
+            Action::Move(Heading::West),
+            Action::AdvanceSprint,
+        ];
+        for action in script.into_iter().cycle().take(40) {
+            engine.apply(action);
+        }
+    }
+
+    fn revealed(engine: &GameEngine) -> Vec<(bool, bool)> {
+        engine.tiles.iter().flatten().map(|tile| (tile.revealed, tile.hidden)).collect()
+    }
+
+    #[test]
+    fn same_seed_and_actions_play_out_identically() {
+        let (mut a, mut b) = (engine(42), engine(42));
+        play_script(&mut a);
+        play_script(&mut b);
+        assert!(a.data().sprint > 0);
+        assert_eq!(a.data(), b.data());
+        assert_eq!(revealed(&a), revealed(&b));
+    }
+
+    #[test]
+    fn month_close_credits_before_the_burn() {
+        let mut engine = engine(7);
+        engine.apply(Action::AdvanceSprint);
+        assert_eq!(engine.data().sprint, 1);
+
+        // The burn alone would empty the budget, but the month closing with this sprint pays out first
+        let burn = engine.data().calculate_burn_rate();
+        engine.data.budget = burn;
+        engine.realizing.push(RealizingSavings { from_sprint: 0, amount: 500 });
+        let events = engine.apply(Action::AdvanceSprint);
+
+        assert_eq!(events[0], GameEvent::SprintProcessed { sprint: 2, burn });
+        assert_eq!(events[1], GameEvent::MonthClosed { month: 1, credited: 500 });
+        assert_eq!(engine.data().budget, 500);
+        assert_eq!(engine.data().realized_savings, 500);
+        assert!(!engine.data().game_lost);
+    }
+
+    #[test]
+    fn restart_rebuilds_the_initial_map() {
+        let fresh = engine(99);
+        let mut played = engine(99);
+        play_script(&mut played);
+
+        let restarted = played.restart();
+        assert_eq!(restarted.data(), fresh.data());
+        assert_eq!(restarted.grid, fresh.grid);
+        assert_eq!(restarted.terrain, fresh.terrain);
+        assert_eq!(revealed(&restarted), revealed(&fresh));
+    }
+
+    #[test]
+    fn clone_keeps_the_rng_position() {
+        let mut original = engine(3);
+        play_script(&mut original);
+        let mut copy = original.clone();
+        assert_eq!(copy.rng.next_u64(), original.rng.next_u64());
+
+        play_script(&mut original);
+        play_script(&mut copy);
+        assert_eq!(copy.data(), original.data());
+    }
+
+    #[test]
+    fn hidden_waste_is_billed_and_reported_as_undiscovered() {
+        let engine = (0..100)
+            .map(engine)
+            .find(|engine| engine.tiles.iter().flatten().any(|tile| tile.hidden))
+            .expect("some seed hides a resource");
+        let data = engine.data();
+        assert!(data.undiscovered_waste > 0);
+        assert!(data.undiscovered_waste < data.total_waste);
+
+        let mut visible = engine.tiles.clone();
+        for tile in visible.iter_mut().flatten().filter(|tile| tile.hidden) {
+            tile.fixed = true;
+        }
+        assert_eq!(engine.total_waste(&visible, &engine.neighborhood), data.total_waste - data.undiscovered_waste);
+    }
+
+    #[test]
+    fn clone_keeps_the_presets_win_conditions() {
+        let mut config = GameConfig::builtin();
+        config.settings_mut(Difficulty::Normal).win_conditions = VictorySet::NONE.with(VictoryType::Savings);
+        let data = GameData::with_config(Difficulty::Normal, 8, 8, Arc::new(config));
+        let mut copy = GameEngine::new(data, GameRng::new(5)).clone();
+
+        // Waste this low would be an Elimination Victory if that path were enabled
+        copy.data.sprint = copy.data.config.min_sprints_to_win;
+        copy.data.total_waste = 0;
+        copy.data.check_win_condition(&copy.win_conditions);
+        assert!(!copy.data().game_won);
+    }
+
+    #[test]
+    fn determinize_starts_imagined_resources_afresh() {
+        let mut engine = engine(11);
+        for _ in 0..12 {
+            engine.apply(Action::AdvanceSprint);
+        }
+        let unseen: Vec<(u8, u8)> = (0..engine.grid_width())
+            .flat_map(|x| (0..engine.grid_height()).map(move |y| (x, y)))
+            .filter(|&(x, y)| !engine.tile(x, y).revealed)
+            .collect();
+        // Leftovers from whatever really sits under the fog
+        for &(x, y) in &unseen {
+            let tile = &mut engine.tiles[x as usize][y as usize];
+            tile.decay_multiplier = 7.0;
+            tile.outage_sprints_left = 3;
+        }
+
+        let resource = engine.catalog.find("IdleVM").unwrap();
+        let grid = vec![vec![resource; engine.grid_height() as usize]; engine.grid_width() as usize];
+        let imagined = engine.determinize(&grid, GameRng::new(1));
+        let level = imagined.decay_level(resource, imagined.data().sprint);
+        assert!(level > 1.0);
+        for &(x, y) in &unseen {
+            let tile = imagined.tile(x, y);
+            assert_eq!(tile.outage_sprints_left, 0);
+            let expected = if imagined.resource_at(x, y).is_empty() { 1.0 } else { level };
+            assert_eq!(tile.decay_multiplier, expected, "({}, {})", x, y);
+        }
+        let mut settled = imagined.clone();
+        settled.apply_decay();
+        settled.recalculate_waste();
+        assert_eq!(settled.data().total_waste, imagined.data().total_waste);
+    }
+}
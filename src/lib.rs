@@ -1,3 +1,11 @@
+pub mod catalog;
 pub mod components;
+pub mod config;
+pub mod engine;
+pub mod navigation;
 pub mod resources;
-pub mod simulator;
\ No newline at end of file
+pub mod simulator;
+pub mod solver;
+pub mod strategy;
+pub mod tuner;
+pub mod win_conditions;
\ No newline at end of file
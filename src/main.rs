@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 
-mod components;
-mod resources;
 mod systems;
 mod ui;
-mod simulator;
 
+use pirate_finops::{catalog, components, config, engine, resources, solver};
+
+use catalog::*;
+use components::GameCamera;
+use config::*;
 use resources::*;
 use systems::*;
 use ui::*;
@@ -21,45 +23,78 @@ enum GameState {
 fn main() {
     let mut app = App::new();
 
-    app.add_plugins(DefaultPlugins.set(WindowPlugin {
-        primary_window: Some(Window {
-            title: "Pirate FinOps Treasure Hunt".into(),
-            resolution: (960., 720.).into(),
-            canvas: Some("#game-canvas".into()),
-            prevent_default_event_handling: false,
-            ..default()
-        }),
-        ..default()
-    }));
+    app.add_plugins(
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "Pirate FinOps Treasure Hunt".into(),
+                    resolution: (960., 720.).into(),
+                    canvas: Some("#game-canvas".into()),
+                    prevent_default_event_handling: false,
+                    ..default()
+                }),
+                ..default()
+            })
+            .set(AssetPlugin {
+                // Hot-reload data assets (e.g. the resource catalog) in native debug builds
+                watch_for_changes_override: Some(cfg!(all(debug_assertions, not(target_family = "wasm")))),
+                ..default()
+            }),
+    );
 
     app.init_state::<GameState>()
-        .insert_resource(GameData::new(Difficulty::Normal))
         .init_resource::<SelectedTile>()
-        .add_systems(Startup, setup_camera)
+        .init_resource::<PlannedRoute>()
+        .init_resource::<GameRng>()
+        .init_resource::<Difficulty>()
+        .init_resource::<MapSize>()
+        .init_asset::<ResourceCatalog>()
+        .init_asset_loader::<ResourceCatalogLoader>()
+        .init_asset::<GameConfig>()
+        .init_asset_loader::<GameConfigLoader>()
+        .add_systems(Startup, (setup_camera, load_resource_catalog, load_game_config))
+        .add_systems(Update, (hot_reload_resource_catalog, hot_reload_game_config))
         .add_systems(OnEnter(GameState::Menu), setup_menu)
-        .add_systems(Update, menu_system.run_if(in_state(GameState::Menu)))
+        .add_systems(Update, (menu_system, seed_input_system, difficulty_select_system, map_size_select_system).run_if(in_state(GameState::Menu)))
         .add_systems(OnExit(GameState::Menu), cleanup_menu)
         .add_systems(OnEnter(GameState::Playing), (setup_game, setup_ui))
         .add_systems(
             Update,
             (
                 handle_player_movement,
+                plan_route_on_click,
+                sail_planned_route,
                 auto_reveal_current_tile,
                 process_tile_fix,
                 update_game_state,
+                update_decay_labels,
+                animate_floating_text,
+                camera_follow_player,
+                camera_zoom,
+                camera_drag_pan,
+                draw_planned_route,
                 update_ui,
+                update_route_text,
+                update_forecast_ui,
                 check_win_condition,
             )
                 .run_if(in_state(GameState::Playing)),
         )
+        // Debug overlay, kept out of release builds since it shows unexplored tiles
+        .add_systems(
+            Update,
+            (toggle_neighborhood_overlay, update_neighborhood_overlay)
+                .run_if(in_state(GameState::Playing))
+                .run_if(|| cfg!(debug_assertions)),
+        )
         .add_systems(OnExit(GameState::Playing), cleanup_game)
         .add_systems(OnEnter(GameState::GameOver), setup_game_over)
-        .add_systems(Update, game_over_system.run_if(in_state(GameState::GameOver)))
+        .add_systems(Update, (game_over_system, update_par_text).run_if(in_state(GameState::GameOver)))
         .add_systems(OnExit(GameState::GameOver), cleanup_game_over);
 
     app.run();
 }
 
 fn setup_camera(mut commands: Commands) {
-    commands.spawn(Camera2dBundle::default());
+    commands.spawn((Camera2dBundle::default(), GameCamera::default()));
 }
@@ -0,0 +1,291 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use crate::components::Terrain;
+use crate::engine::Heading;
+
+// Sailing rules shared by the engine, map generation and route planning.
+// Terrain is indexed [x][y] like the resource grid.
+
+// Where one move ends up. Sailing into a current carries the ship one tile further for free.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub entered: (u8, u8),
+    pub landed: (u8, u8),
+    pub cost_multiplier: i32,
+}
+
+pub fn step(terrain: &[Vec<Terrain>], x: u8, y: u8, heading: Heading) -> Option<Step> {
+    let entered = neighbor(terrain, x, y, heading).filter(|&(nx, ny)| terrain_at(terrain, nx, ny).is_passable())?;
+    let entered_terrain = terrain_at(terrain, entered.0, entered.1);
+
+    // A current only pushes when the tile it points at is open
+    let landed = match entered_terrain {
+        Terrain::Current(drift) => neighbor(terrain, entered.0, entered.1, drift)
+            .filter(|&(px, py)| terrain_at(terrain, px, py).is_passable())
+            .unwrap_or(entered),
+        _ => entered,
+    };
+
+    Some(Step {
+        entered,
+        landed,
+        cost_multiplier: entered_terrain.cost_multiplier(),
+    })
+}
+
+// Cheapest route from `from` to `to` (A*). Returns the headings to sail, or None when unreachable.
+pub fn find_path(terrain: &[Vec<Terrain>], move_cost: i32, from: (u8, u8), to: (u8, u8)) -> Option<Vec<Heading>> {
+    let (width, height) = dimensions(terrain);
+    let index = |(x, y): (u8, u8)| x as usize * height + y as usize;
+
+    // A current can carry the ship two tiles for the price of one, so halve the distance estimate
+    let estimate = |(x, y): (u8, u8)| {
+        let distance = (x as i32 - to.0 as i32).abs() + (y as i32 - to.1 as i32).abs();
+        distance * move_cost / 2
+    };
+
+    let mut best_cost = vec![i32::MAX; width * height];
+    let mut came_from: Vec<Option<((u8, u8), Heading)>> = vec![None; width * height];
+    let mut open = BinaryHeap::new();
+
+    best_cost[index(from)] = 0;
+    open.push(Reverse((estimate(from), 0, from)));
+
+    while let Some(Reverse((_, cost, position))) = open.pop() {
+        if position == to {
+            let mut path = Vec::new();
+            let mut current = to;
+            while let Some((previous, heading)) = came_from[index(current)] {
+                path.push(heading);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if cost > best_cost[index(position)] {
+            continue;
+        }
+
+        for heading in Heading::ALL {
+            let Some(step) = step(terrain, position.0, position.1, heading) else {
+                continue;
+            };
+            let next_cost = cost + move_cost * step.cost_multiplier;
+            if next_cost < best_cost[index(step.landed)] {
+                best_cost[index(step.landed)] = next_cost;
+                came_from[index(step.landed)] = Some((position, heading));
+                open.push(Reverse((next_cost + estimate(step.landed), next_cost, step.landed)));
+            }
+        }
+    }
+    None
+}
+
+// Cheapest cost of sailing from `from` to every tile (Dijkstra), None where the ship can't get to
+pub fn sailing_costs(terrain: &[Vec<Terrain>], move_cost: i32, from: (u8, u8)) -> Vec<Vec<Option<i32>>> {
+    let (width, height) = dimensions(terrain);
+    let mut costs = vec![vec![None; height]; width];
+    let mut open = BinaryHeap::new();
+
+    costs[from.0 as usize][from.1 as usize] = Some(0);
+    open.push(Reverse((0, from)));
+
+    while let Some(Reverse((cost, (x, y)))) = open.pop() {
+        if costs[x as usize][y as usize].is_some_and(|best| cost > best) {
+            continue;
+        }
+        for heading in Heading::ALL {
+            let Some(step) = step(terrain, x, y, heading) else {
+                continue;
+            };
+            let next_cost = cost + move_cost * step.cost_multiplier;
+            let (lx, ly) = step.landed;
+            if costs[lx as usize][ly as usize].is_none_or(|best| next_cost < best) {
+                costs[lx as usize][ly as usize] = Some(next_cost);
+                open.push(Reverse((next_cost, step.landed)));
+            }
+        }
+    }
+    costs
+}
+
+// Every tile the ship can come to rest on, starting from `from`
+pub fn reachable(terrain: &[Vec<Terrain>], from: (u8, u8)) -> Vec<Vec<bool>> {
+    let (width, height) = dimensions(terrain);
+    let mut seen = vec![vec![false; height]; width];
+    seen[from.0 as usize][from.1 as usize] = true;
+
+    let mut queue = vec![from];
+    while let Some((x, y)) = queue.pop() {
+        for heading in Heading::ALL {
+            if let Some(step) = step(terrain, x, y, heading) {
+                let (lx, ly) = step.landed;
+                if !seen[lx as usize][ly as usize] {
+                    seen[lx as usize][ly as usize] = true;
+                    queue.push(step.landed);
+                }
+            }
+        }
+    }
+    seen
+}
+
+fn dimensions(terrain: &[Vec<Terrain>]) -> (usize, usize) {
+    (terrain.len(), terrain.first().map_or(0, |column| column.len()))
+}
+
+fn terrain_at(terrain: &[Vec<Terrain>], x: u8, y: u8) -> Terrain {
+    terrain[x as usize][y as usize]
+}
+
+fn neighbor(terrain: &[Vec<Terrain>], x: u8, y: u8, heading: Heading) -> Option<(u8, u8)> {
+    let (width, height) = dimensions(terrain);
+    let (dx, dy) = heading.delta();
+    let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+    if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+        return None;
+    }
+    Some((nx as u8, ny as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOVE_COST: i32 = 10;
+
+    fn sea(width: usize, height: usize) -> Vec<Vec<Terrain>> {
+        vec![vec![Terrain::OpenWater; height]; width]
+    }
+
+    // Sails `path` from `from`, returning where the ship ends up and what it paid
+    fn sail(terrain: &[Vec<Terrain>], from: (u8, u8), path: &[Heading]) -> ((u8, u8), i32) {
+        let mut position = from;
+        let mut cost = 0;
+        for &heading in path {
+            let step = step(terrain, position.0, position.1, heading).expect("route stays on passable water");
+            position = step.landed;
+            cost += MOVE_COST * step.cost_multiplier;
+        }
+        (position, cost)
+    }
+
+    // Reef wall with one gap, a storm, and currents that speed or block a shortcut
+    //
+    //   y=4  .  .  .  .  .  .
+    //   y=3  .  R  .  >  >  .
+    //   y=2  .  R  S  .  .  .
+    //   y=1  .  R  .  v  R  .
+    //   y=0  .  .  .  .  R  .
+    //       x=0 1  2  3  4  5
+    fn chart() -> Vec<Vec<Terrain>> {
+        let mut terrain = sea(6, 5);
+        for (x, y) in [(1, 1), (1, 2), (1, 3), (4, 0), (4, 1)] {
+            terrain[x][y] = Terrain::Reef;
+        }
+        terrain[2][2] = Terrain::Storm;
+        terrain[3][3] = Terrain::Current(Heading::East);
+        terrain[4][3] = Terrain::Current(Heading::East);
+        terrain[3][1] = Terrain::Current(Heading::South);
+        terrain
+    }
+
+    #[test]
+    fn step_follows_currents_and_stops_at_obstacles() {
+        let terrain = chart();
+        // Into a current: carried one tile further for the price of one move
+        let pushed = step(&terrain, 2, 3, Heading::East).unwrap();
+        assert_eq!((pushed.entered, pushed.landed, pushed.cost_multiplier), ((3, 3), (4, 3), 1));
+        // The current points at (3, 0), which is open, so the push goes through
+        assert_eq!(step(&terrain, 2, 1, Heading::East).unwrap().landed, (3, 0));
+        // Storms cost double
+        assert_eq!(step(&terrain, 2, 1, Heading::North).unwrap().cost_multiplier, 2);
+        // Reefs and the map's edge block the move
+        assert_eq!(step(&terrain, 0, 2, Heading::East), None);
+        assert_eq!(step(&terrain, 0, 0, Heading::West), None);
+        assert_eq!(step(&terrain, 0, 0, Heading::South), None);
+    }
+
+    #[test]
+    fn current_pushing_into_an_obstacle_leaves_the_ship_on_it() {
+        let mut terrain = sea(4, 1);
+        terrain[1][0] = Terrain::Current(Heading::East);
+        terrain[2][0] = Terrain::Island;
+        let blocked = step(&terrain, 0, 0, Heading::East).unwrap();
+        assert_eq!((blocked.entered, blocked.landed), ((1, 0), (1, 0)));
+
+        // Pushed off the map's edge is blocked too
+        let mut terrain = sea(2, 1);
+        terrain[1][0] = Terrain::Current(Heading::East);
+        assert_eq!(step(&terrain, 0, 0, Heading::East).unwrap().landed, (1, 0));
+    }
+
+    #[test]
+    fn current_chain_carries_the_ship_two_tiles_a_move() {
+        let mut terrain = sea(7, 1);
+        for column in &mut terrain[1..6] {
+            column[0] = Terrain::Current(Heading::East);
+        }
+        let path = find_path(&terrain, MOVE_COST, (0, 0), (6, 0)).unwrap();
+        assert_eq!(path, vec![Heading::East; 3]);
+        assert_eq!(sail(&terrain, (0, 0), &path), ((6, 0), 3 * MOVE_COST));
+        assert_eq!(sailing_costs(&terrain, MOVE_COST, (0, 0))[6][0], Some(3 * MOVE_COST));
+        // Each push lands on a current tile without being pushed again
+        assert_eq!(step(&terrain, 0, 0, Heading::East).unwrap().landed, (2, 0));
+    }
+
+    #[test]
+    fn find_path_costs_match_dijkstra_everywhere() {
+        let terrain = chart();
+        for from in [(0, 0), (2, 1), (5, 4), (3, 0)] {
+            let costs = sailing_costs(&terrain, MOVE_COST, from);
+            for x in 0..6 {
+                for y in 0..5 {
+                    let path = find_path(&terrain, MOVE_COST, from, (x, y));
+                    match costs[x as usize][y as usize] {
+                        Some(cost) => {
+                            let path = path.unwrap_or_else(|| panic!("no path from {:?} to {:?}", from, (x, y)));
+                            assert_eq!(sail(&terrain, from, &path), ((x, y), cost), "from {:?} to {:?}", from, (x, y));
+                        }
+                        None => assert_eq!(path, None, "from {:?} to {:?}", from, (x, y)),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_path_routes_around_the_reef() {
+        let terrain = chart();
+        let path = find_path(&terrain, MOVE_COST, (0, 2), (2, 2)).unwrap();
+        // Five moves round either end of the wall, then a sixth into the storm at double cost
+        assert_eq!(sail(&terrain, (0, 2), &path), ((2, 2), 7 * MOVE_COST));
+        assert_eq!(find_path(&terrain, MOVE_COST, (2, 2), (2, 2)), Some(Vec::new()));
+    }
+
+    #[test]
+    fn walled_off_tiles_are_unreachable() {
+        let mut terrain = sea(5, 5);
+        for (x, y) in [(2, 3), (2, 1), (1, 2), (3, 2)] {
+            terrain[x][y] = Terrain::Reef;
+        }
+        assert_eq!(find_path(&terrain, MOVE_COST, (0, 0), (2, 2)), None);
+        assert_eq!(sailing_costs(&terrain, MOVE_COST, (0, 0))[2][2], None);
+        let reachable = reachable(&terrain, (0, 0));
+        assert!(!reachable[2][2]);
+        assert!(!reachable[2][3], "reefs can't be sailed onto");
+        assert!(reachable[4][4]);
+    }
+
+    #[test]
+    fn reachable_matches_sailing_costs() {
+        let terrain = chart();
+        let reachable = reachable(&terrain, (0, 0));
+        let costs = sailing_costs(&terrain, MOVE_COST, (0, 0));
+        for x in 0..6 {
+            for y in 0..5 {
+                assert_eq!(reachable[x][y], costs[x][y].is_some(), "({}, {})", x, y);
+            }
+        }
+    }
+}
@@ -1,23 +1,38 @@
+use std::sync::Arc;
 use bevy::prelude::*;
 use rand::prelude::*;
-use crate::components::ResourceType;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use crate::catalog::ResourceCatalog;
+use crate::config::GameConfig;
+use crate::components::{ResourceType, Terrain};
+use crate::engine::{Heading, RoutePreview};
+use crate::navigation;
+use crate::win_conditions::{VictorySet, VictoryType, WinConditions};
 
-#[derive(Resource, Default)]
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
 pub struct GameData {
     pub budget: i32,
     pub sprint: u32,
-    pub total_waste: i32,
+    pub total_waste: i32,  // Monthly waste of every unfixed resource, hidden ones included
+    pub undiscovered_waste: i32,  // The part of `total_waste` on tiles still hidden from the player
     pub monthly_savings: i32,
-    pub grid_size: u8,
+    pub realized_savings: i32,  // Savings credited back to the budget so far
+    pub estimated_savings: i32,  // What completed fixes were estimated to save per month
+    pub grid_width: u8,
+    pub grid_height: u8,
     pub difficulty: Difficulty,
+    pub settings: DifficultySettings,
+    pub config: Arc<GameConfig>,
     pub game_won: bool,
     pub game_lost: bool,
+    pub victory: Option<VictoryType>,  // Which win condition ended the game
+    pub loss: Option<LossCause>,  // What ended a lost game
     pub player_x: u8,
     pub player_y: u8,
     pub combo_multiplier: f32,
     pub resources_fixed_in_order: Vec<ResourceType>,
     pub adjacency_bonus: i32,
-    pub last_decay_sprint: u32,  // Track when we last applied decay
     pub hidden_waste_discovered: i32,  // Waste revealed through fixing certain resources
     pub cascade_failures: Vec<(u8, u8)>,  // Positions of cascading failures
     pub failed_fix_attempts: u32,  // Total failed attempts
@@ -26,27 +41,65 @@ pub struct GameData {
     pub critical_resources_found: u32,  // Number of critical resources discovered
 }
 
+// Why a game was lost. The engine only ends games on a budget, Stranded is for a player
+// (or bot) that stopped with budget left and no win.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LossCause {
+    Burn,  // The sprint's burn emptied the budget
+    Sailing,  // A move emptied the budget
+    Stranded,
+}
+
+impl LossCause {
+    pub const ALL: [LossCause; 3] = [Self::Burn, Self::Sailing, Self::Stranded];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Burn => "Burned Out",
+            Self::Sailing => "Sunk by Sailing Costs",
+            Self::Stranded => "Stranded",
+        }
+    }
+}
+
 impl GameData {
     pub fn new(difficulty: Difficulty) -> Self {
-        // Always use Normal difficulty settings
-        let budget = 30000;  // More generous starting budget
-        let grid_size = 8;
+        let config = GameConfig::builtin();
+        let settings = config.settings(difficulty);
+        Self::with_config(difficulty, settings.grid_width, settings.grid_height, Arc::new(config))
+    }
+
+    // A preset from `config` played on a custom map size. The budget scales with the map area,
+    // since burn grows with the amount of waste on the map.
+    pub fn with_config(difficulty: Difficulty, width: u8, height: u8, config: Arc<GameConfig>) -> Self {
+        let settings = config.settings(difficulty);
+        let width = width.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        let height = height.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        let preset_area = settings.grid_width as i64 * settings.grid_height as i64;
+        let budget = (settings.budget as i64 * width as i64 * height as i64 / preset_area) as i32;
 
         Self {
             budget,
             sprint: 0,
             total_waste: 0,
+            undiscovered_waste: 0,
             monthly_savings: 0,
-            grid_size,
+            realized_savings: 0,
+            estimated_savings: 0,
+            grid_width: width,
+            grid_height: height,
             difficulty,
+            settings,
+            config,
             game_won: false,
             game_lost: false,
-            player_x: grid_size / 2,
-            player_y: grid_size / 2,
+            victory: None,
+            loss: None,
+            player_x: width / 2,
+            player_y: height / 2,
             combo_multiplier: 1.0,
             resources_fixed_in_order: Vec::new(),
             adjacency_bonus: 0,
-            last_decay_sprint: 0,
             hidden_waste_discovered: 0,
             cascade_failures: Vec::new(),
             failed_fix_attempts: 0,
@@ -63,18 +116,34 @@ impl GameData {
 
         if self.budget <= 0 {
             self.game_lost = true;
+            self.loss = Some(LossCause::Burn);
         }
     }
 
+    // Actual monthly savings as a share of the estimates (1.0 = spot on), once anything is fixed
+    pub fn estimate_accuracy(&self) -> Option<f32> {
+        (self.estimated_savings > 0).then(|| self.monthly_savings as f32 / self.estimated_savings as f32)
+    }
+
+    // Month the current sprint falls in, counting from 1
+    pub fn month(&self) -> u32 {
+        self.sprint / self.config.sprints_per_month + 1
+    }
+
     pub fn calculate_burn_rate(&self) -> i32 {
-        let base_burn = 30;
+        let base_burn = self.config.base_burn;
 
         // Simple linear waste burn
-        let waste_burn = (self.total_waste as f32 * 0.4) as i32;
+        let waste_burn = (self.total_waste as f32 * self.settings.burn_coefficient) as i32;
 
         base_burn + waste_burn
     }
 
+    // Whole sprints the budget can still pay for at the current burn rate
+    pub fn runway_sprints(&self) -> i32 {
+        self.budget.max(0) / self.calculate_burn_rate().max(1)
+    }
+
     pub fn waste_percentage(&self) -> f32 {
         if self.calculate_burn_rate() == 0 {
             return 0.0;
@@ -82,23 +151,150 @@ impl GameData {
         (self.total_waste as f32 / self.calculate_burn_rate() as f32) * 100.0
     }
 
-    pub fn check_win_condition(&mut self) {
-        // Only check win condition after at least 5 sprints
-        if self.sprint < 5 {
+    pub fn check_win_condition(&mut self, conditions: &WinConditions) {
+        // Only check win condition after the first few sprints
+        if self.sprint < self.config.min_sprints_to_win {
             return;
         }
 
-        // Simple win condition: reduce waste significantly
-        if self.waste_percentage() <= 30.0 && self.monthly_savings >= 1500 {
+        if let Some(victory) = conditions.check(self) {
             self.game_won = true;
+            self.victory = Some(victory);
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
 pub enum Difficulty {
+    Easy,
     #[default]
     Normal,
+    Hard,
+    Nightmare,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [Self::Easy, Self::Normal, Self::Hard, Self::Nightmare];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Nightmare => "Nightmare",
+        }
+    }
+
+    // Case-insensitive lookup by name, for command-line tools
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|d| d == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(&self) -> Self {
+        let index = Self::ALL.iter().position(|d| d == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+// Map dimensions the game supports, in tiles
+pub const MIN_GRID_SIZE: u8 = 4;
+pub const MAX_GRID_SIZE: u8 = 48;
+
+// Tuning knobs that a difficulty preset controls (set in the game config)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DifficultySettings {
+    pub budget: i32,
+    pub grid_width: u8,
+    pub grid_height: u8,
+    pub resource_density: f32,  // Share of tiles that hold a resource
+    pub move_cost: i32,  // Budget spent per tile sailed
+    pub burn_coefficient: f32,  // Share of unfixed waste burned each sprint
+    pub decay_scale: f32,  // Scales how far each resource's decay curve climbs above 1.0
+    pub terrain_density: f32,  // Share of empty tiles charted as reefs, islands, storms or currents
+    #[serde(default)]
+    pub win_conditions: VictorySet,  // Victory paths this preset offers
+}
+
+// Map size picked on the menu. `Preset` uses the difficulty's own map.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MapSize {
+    #[default]
+    Preset,
+    Wide,
+    Large,
+    Estate,
+}
+
+impl MapSize {
+    pub const ALL: [MapSize; 4] = [Self::Preset, Self::Wide, Self::Large, Self::Estate];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Preset => "Preset",
+            Self::Wide => "Wide",
+            Self::Large => "Large",
+            Self::Estate => "Estate",
+        }
+    }
+
+    pub fn dimensions(&self, settings: &DifficultySettings) -> (u8, u8) {
+        match self {
+            Self::Preset => (settings.grid_width, settings.grid_height),
+            Self::Wide => (16, 10),
+            Self::Large => (24, 24),
+            Self::Estate => (MAX_GRID_SIZE, MAX_GRID_SIZE),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|m| m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+// Where the map sits in world space: tile (0, 0) is at `origin` and the map is centered on (0, 0)
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TileLayout {
+    pub tile_size: f32,
+    pub origin: Vec2,
+    pub width: u8,
+    pub height: u8,
+}
+
+impl TileLayout {
+    pub const TILE_SIZE: f32 = 64.0;
+
+    pub fn centered(width: u8, height: u8, tile_size: f32) -> Self {
+        let origin = Vec2::new(
+            -(width as f32 * tile_size) / 2.0 + tile_size / 2.0,
+            -(height as f32 * tile_size) / 2.0 + tile_size / 2.0,
+        );
+        Self { tile_size, origin, width, height }
+    }
+
+    // World position of a tile's center
+    pub fn world_position(&self, x: u8, y: u8) -> Vec2 {
+        self.origin + Vec2::new(x as f32, y as f32) * self.tile_size
+    }
+
+    // Size of the whole map in world units
+    pub fn map_size(&self) -> Vec2 {
+        Vec2::new(self.width as f32, self.height as f32) * self.tile_size
+    }
+
+    // Tile under a world position, if it is on the map
+    pub fn tile_at(&self, world: Vec2) -> Option<(u8, u8)> {
+        let tile = ((world - self.origin) / self.tile_size).round();
+        if tile.x < 0.0 || tile.y < 0.0 || tile.x >= self.width as f32 || tile.y >= self.height as f32 {
+            return None;
+        }
+        Some((tile.x as u8, tile.y as u8))
+    }
 }
 
 #[derive(Resource, Default)]
@@ -107,57 +303,156 @@ pub struct SelectedTile {
     pub position: Option<(u8, u8)>,
 }
 
-pub fn generate_random_grid(size: u8) -> Vec<Vec<ResourceType>> {
-    let mut rng = thread_rng();
-    let mut grid = vec![vec![ResourceType::Empty; size as usize]; size as usize];
-
-    let resource_types = vec![
-        // High value (more rare)
-        ResourceType::IdleGPUCluster,
-        ResourceType::OverprovisionedOpenShift,
-        ResourceType::MissedReservation,
-
-        // Medium-high value (common)
-        ResourceType::IdleVM,
-        ResourceType::IdleVM,  // Duplicate for higher frequency
-        ResourceType::OversizedAppService,
-        ResourceType::UnusedSQLDatabase,
-        ResourceType::OverprovisionedCosmosDB,
-
-        // Medium value (common)
-        ResourceType::LogIngestionBloat,
-        ResourceType::LogIngestionBloat,  // Duplicate for higher frequency
-        ResourceType::ExpiredBackups,
-        ResourceType::RedundantLoadBalancer,
-
-        // Low value (very common)
-        ResourceType::OrphanedDisk,
-        ResourceType::OrphanedDisk,  // Duplicate
-        ResourceType::StaleSnapshot,
-        ResourceType::StaleSnapshot,  // Duplicate
-        ResourceType::UnusedPublicIP,
-        ResourceType::UntaggedResource,
-        ResourceType::UntaggedResource,  // Duplicate
-        ResourceType::IdleCDNEndpoint,
-        ResourceType::EmptyStorageAccount,
-    ];
-
-    let total_tiles = (size * size) as usize;
-    let resource_count = (total_tiles as f32 * 0.40) as usize;  // 40% of tiles have resources
+// Route picked by clicking a tile. Clicking the same tile again sets sail along it.
+#[derive(Resource, Default)]
+pub struct PlannedRoute {
+    pub target: Option<(u8, u8)>,
+    pub headings: Vec<Heading>,
+    pub preview: RoutePreview,
+    pub sailing: bool,
+    pub step_timer: Timer,
+}
+
+impl PlannedRoute {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// Seeded RNG that every random roll in a game derives from (map, savings, events).
+// StdRng is platform independent, so a seed plays out identically on native and WASM.
+#[derive(Resource, Clone)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Random seeds are kept short so players can read them out and type them back in
+    pub fn from_entropy() -> Self {
+        Self::new(thread_rng().gen::<u32>() as u64)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+// Resources a map of this size holds, before any are fixed
+pub fn resource_count(width: u8, height: u8, resource_density: f32) -> usize {
+    let total_tiles = width as usize * height as usize;
+    (total_tiles as f32 * resource_density) as usize
+}
+
+pub fn generate_random_grid(
+    width: u8,
+    height: u8,
+    resource_density: f32,
+    catalog: &ResourceCatalog,
+    rng: &mut GameRng,
+) -> Vec<Vec<ResourceType>> {
+    let mut grid = vec![vec![ResourceType::EMPTY; height as usize]; width as usize];
+
+    let resource_count = resource_count(width, height, resource_density);
 
     let mut positions: Vec<(usize, usize)> = Vec::new();
-    for x in 0..size as usize {
-        for y in 0..size as usize {
+    for x in 0..width as usize {
+        for y in 0..height as usize {
             positions.push((x, y));
         }
     }
-    positions.shuffle(&mut rng);
+    positions.shuffle(rng);
 
-    for i in 0..resource_count {
-        let (x, y) = positions[i];
-        let resource = resource_types.choose(&mut rng).unwrap().clone();
-        grid[x][y] = resource;
+    for &(x, y) in positions.iter().take(resource_count) {
+        grid[x][y] = catalog.random_resource(rng);
     }
 
     grid
-}
\ No newline at end of file
+}
+// Charts reefs, islands, storms and currents onto empty tiles. Obstacles are never allowed to cut a
+// resource off from the starting tile, and currents that would strand one are calmed to open water.
+pub fn generate_terrain(
+    grid: &[Vec<ResourceType>],
+    start: (u8, u8),
+    terrain_density: f32,
+    rng: &mut GameRng,
+) -> Vec<Vec<Terrain>> {
+    let (width, height) = (grid.len(), grid.first().map_or(0, |column| column.len()));
+    let mut terrain = vec![vec![Terrain::OpenWater; height]; width];
+
+    let mut candidates: Vec<(usize, usize)> = Vec::new();
+    for (x, column) in grid.iter().enumerate() {
+        for (y, resource) in column.iter().enumerate() {
+            if resource.is_empty() && (x, y) != (start.0 as usize, start.1 as usize) {
+                candidates.push((x, y));
+            }
+        }
+    }
+    candidates.shuffle(rng);
+    let terrain_count = (candidates.len() as f32 * terrain_density) as usize;
+
+    for &(x, y) in candidates.iter().take(terrain_count) {
+        let kind = match rng.gen_range(0..20) {
+            0..=6 => Terrain::Reef,
+            7..=11 => Terrain::Island,
+            12..=16 => Terrain::Storm,
+            _ => Terrain::Current(*Heading::ALL.choose(rng).unwrap()),
+        };
+        terrain[x][y] = kind;
+
+        if !kind.is_passable() && !all_resources_reachable(&terrain, grid, start) {
+            terrain[x][y] = Terrain::OpenWater;
+        }
+    }
+
+    while !all_resources_reachable(&terrain, grid, start) {
+        let Some(current) = terrain
+            .iter_mut()
+            .flatten()
+            .find(|t| matches!(t, Terrain::Current(_)))
+        else {
+            break;
+        };
+        *current = Terrain::OpenWater;
+    }
+
+    terrain
+}
+
+fn all_resources_reachable(terrain: &[Vec<Terrain>], grid: &[Vec<ResourceType>], start: (u8, u8)) -> bool {
+    let reachable = navigation::reachable(terrain, start);
+    grid.iter()
+        .zip(&reachable)
+        .all(|(resources, seen)| resources.iter().zip(seen).all(|(resource, &seen)| resource.is_empty() || seen))
+}
@@ -1,222 +1,837 @@
-use crate::components::ResourceType;
-use crate::resources::{Difficulty, GameData, generate_random_grid};
-use rand::prelude::*;
-
-#[derive(Debug)]
-struct SimulationResult {
-    won: bool,
-    sprints: u32,
-    final_budget: i32,
-    final_waste: i32,
-    final_savings: i32,
-    waste_percentage: f32,
-}
-
-pub fn run_simulation_suite(num_simulations: u32, difficulty: Difficulty) -> SimulationReport {
-    let mut wins = 0;
-    let mut total_sprints_to_win = 0;
-    let mut total_sprints_to_lose = 0;
-    let mut min_sprints_to_win = u32::MAX;
-    let mut max_sprints_to_win = 0;
-    let mut avg_final_budget_win = 0;
-    let mut avg_final_budget_lose = 0;
-    let mut waste_percentage_wins = Vec::new();
-    let mut savings_wins = Vec::new();
-
-    for i in 0..num_simulations {
-        let result = simulate_single_game(difficulty);
-
-        if result.won {
-            wins += 1;
-            total_sprints_to_win += result.sprints;
-            min_sprints_to_win = min_sprints_to_win.min(result.sprints);
-            max_sprints_to_win = max_sprints_to_win.max(result.sprints);
-            avg_final_budget_win += result.final_budget;
-            waste_percentage_wins.push(result.waste_percentage);
-            savings_wins.push(result.final_savings);
-        } else {
-            total_sprints_to_lose += result.sprints;
-            avg_final_budget_lose += result.final_budget;
-        }
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use crate::catalog::ResourceCatalog;
+use crate::config::GameConfig;
+use crate::engine::GameEngine;
+use crate::resources::{Difficulty, GameData, GameRng, LossCause};
+use crate::strategy::{self, StrategyKind};
+use crate::win_conditions::VictoryType;
+
+// Outcome of one simulated game
+#[derive(Clone, Debug, Serialize)]
+pub struct GameResult {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub strategy: StrategyKind,
+    pub won: bool,
+    pub victory: Option<VictoryType>,
+    pub loss: Option<LossCause>,
+    pub sprints: u32,
+    pub final_budget: i32,
+    pub final_waste: i32,
+    pub final_savings: i32,
+    pub waste_percentage: f32,
+    pub failed_fixes: u32,
+    pub cascade_failures: u32,
+    pub hidden_waste_discovered: i32,
+    pub bonus_credits: i32,
+    pub realized_savings: i32,
+    pub estimate_accuracy: Option<f32>,
+}
 
-        if i % 20 == 0 {
-            println!("Simulated {} games...", i);
-        }
+impl GameResult {
+    pub const CSV_HEADER: &'static str = "seed,difficulty,strategy,won,victory,loss,sprints,final_budget,final_waste,final_savings,waste_percentage,failed_fixes,cascade_failures,hidden_waste_discovered,bonus_credits,realized_savings,estimate_accuracy";
+
+    pub fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{:.2},{},{},{},{},{},{}",
+            self.seed,
+            self.difficulty.name(),
+            self.strategy.name(),
+            self.won,
+            self.victory.map(|v| format!("{:?}", v)).unwrap_or_default(),
+            self.loss.map(|l| format!("{:?}", l)).unwrap_or_default(),
+            self.sprints,
+            self.final_budget,
+            self.final_waste,
+            self.final_savings,
+            self.waste_percentage,
+            self.failed_fixes,
+            self.cascade_failures,
+            self.hidden_waste_discovered,
+            self.bonus_credits,
+            self.realized_savings,
+            self.estimate_accuracy.map(|a| format!("{:.4}", a)).unwrap_or_default(),
+        )
     }
+}
 
-    let losses = num_simulations - wins;
-    let win_rate = (wins as f32 / num_simulations as f32) * 100.0;
+// Seeds handed to a worker thread at a time. Chunks are merged back in seed order, so a
+// report comes out the same whatever the thread count.
+const CHUNK_SIZE: u64 = 256;
+// Minimum time between progress lines
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
 
-    let avg_sprints_win = if wins > 0 {
-        total_sprints_to_win as f32 / wins as f32
-    } else {
-        0.0
-    };
+// Every bot's report on one difficulty, all played on the same seeds
+pub struct Matchup {
+    pub reports: Vec<SimulationReport>,
+    pub head_to_head: Vec<Vec<u32>>,  // [row][column]: seeds the row bot won and the column bot lost
+}
 
-    let avg_sprints_lose = if losses > 0 {
-        total_sprints_to_lose as f32 / losses as f32
-    } else {
-        0.0
-    };
+// Game i is played on seed `base_seed + i`, so a suite is reproducible from its base seed.
+// Panics if the last seed would overflow u64.
+pub fn run_simulation_suite(
+    num_simulations: u32,
+    difficulty: Difficulty,
+    strategy: StrategyKind,
+    base_seed: u64,
+    config: &Arc<GameConfig>,
+) -> SimulationReport {
+    let end = base_seed.checked_add(num_simulations as u64).expect("seed range runs past u64::MAX");
+    let seeds = base_seed..end;
+    let catalog = ResourceCatalog::builtin_shared();
+    let mut matchup = run_matchup(difficulty, &[strategy], seeds, config, &catalog, default_threads(), None);
+    matchup.reports.remove(0)
+}
 
-    let avg_budget_win = if wins > 0 {
-        avg_final_budget_win / wins as i32
-    } else {
-        0
-    };
+// Plays the same seeds on every difficulty preset, so the presets are compared on equal maps
+pub fn run_difficulty_suite(
+    num_simulations: u32,
+    strategy: StrategyKind,
+    base_seed: u64,
+    config: &Arc<GameConfig>,
+) -> Vec<SimulationReport> {
+    Difficulty::ALL
+        .iter()
+        .map(|&difficulty| {
+            eprintln!("\n{} difficulty:", difficulty.name());
+            run_simulation_suite(num_simulations, difficulty, strategy, base_seed, config)
+        })
+        .collect()
+}
 
-    let avg_budget_lose = if losses > 0 {
-        avg_final_budget_lose / losses as i32
-    } else {
-        0
-    };
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
 
-    let avg_waste_percentage = if !waste_percentage_wins.is_empty() {
-        waste_percentage_wins.iter().sum::<f32>() / waste_percentage_wins.len() as f32
-    } else {
-        0.0
-    };
+// Receives finished games in seed order
+pub type GameSink<'a> = dyn FnMut(&[GameResult]) + 'a;
 
-    let avg_savings = if !savings_wins.is_empty() {
-        savings_wins.iter().sum::<i32>() / savings_wins.len() as i32
-    } else {
-        0
-    };
+// What a worker sends back for one chunk of seeds
+struct ChunkOutcome {
+    index: u64,
+    seeds: u64,
+    stats: Vec<SimulationStats>,
+    head_to_head: Vec<Vec<u32>>,
+    games: Vec<GameResult>,
+}
 
-    SimulationReport {
-        total_games: num_simulations,
-        wins,
-        losses,
-        win_rate,
-        avg_sprints_to_win: avg_sprints_win,
-        avg_sprints_to_lose: avg_sprints_lose,
-        min_sprints_to_win: if wins > 0 { min_sprints_to_win } else { 0 },
-        max_sprints_to_win,
-        avg_final_budget_win: avg_budget_win,
-        avg_final_budget_lose: avg_budget_lose,
-        avg_waste_percentage_win: avg_waste_percentage,
-        avg_savings_win: avg_savings,
-    }
-}
-
-fn simulate_single_game(difficulty: Difficulty) -> SimulationResult {
-    let mut game_data = GameData::new(difficulty);
-    let grid = generate_random_grid(game_data.grid_size);
-
-    // Calculate initial total waste from all resources on the map
-    let mut initial_waste = 0;
-    for x in 0..game_data.grid_size {
-        for y in 0..game_data.grid_size {
-            let resource = &grid[x as usize][y as usize];
-            if !matches!(resource, ResourceType::Empty) {
-                initial_waste += resource.waste_cost();
-            }
+// Plays every bot on every seed, split across `threads` worker threads. Each game draws from
+// its own seeded RNG, so results don't depend on which thread played them, and only running
+// totals come back, so memory stays flat however many games are played. `on_games` is handed
+// every game in seed order (each seed's bots in `strategies` order) for callers that want them.
+// Progress goes to stderr so stdout stays clean for JSON/CSV output.
+pub fn run_matchup(
+    difficulty: Difficulty,
+    strategies: &[StrategyKind],
+    seeds: Range<u64>,
+    config: &Arc<GameConfig>,
+    catalog: &Arc<ResourceCatalog>,
+    threads: usize,
+    mut on_games: Option<&mut GameSink>,
+) -> Matchup {
+    let total = seeds.end.saturating_sub(seeds.start);
+    let chunks = total.div_ceil(CHUNK_SIZE);
+    let keep_games = on_games.is_some();
+    let next_chunk = AtomicU64::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let mut stats = vec![SimulationStats::default(); strategies.len()];
+    let mut head_to_head = vec![vec![0; strategies.len()]; strategies.len()];
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, chunks.max(1) as usize) {
+            let sender = sender.clone();
+            let next_chunk = &next_chunk;
+            scope.spawn(move || loop {
+                let index = next_chunk.fetch_add(1, Ordering::Relaxed);
+                if index >= chunks {
+                    break;
+                }
+                let start = seeds.start + index * CHUNK_SIZE;
+                let chunk_seeds = start..(start + CHUNK_SIZE).min(seeds.end);
+                let outcome = play_chunk(index, chunk_seeds, difficulty, strategies, config, catalog, keep_games);
+                if sender.send(outcome).is_err() {
+                    break;
+                }
+            });
         }
-    }
-    game_data.total_waste = initial_waste;
-
-    // Simulate optimal play strategy
-    let mut resources_to_fix = Vec::new();
-    for x in 0..game_data.grid_size {
-        for y in 0..game_data.grid_size {
-            let resource = &grid[x as usize][y as usize];
-            if !matches!(resource, ResourceType::Empty) {
-                let waste = resource.waste_cost();
-                let (min_savings, max_savings) = resource.savings_range();
-                let avg_savings = (min_savings + max_savings) / 2;
-                let fix_sprints = resource.fix_sprints();
-                let efficiency = (waste + avg_savings) as f32 / fix_sprints as f32;
-                resources_to_fix.push((waste, avg_savings, fix_sprints, efficiency, x, y));
+        drop(sender);
+
+        // Merge chunks in seed order, holding back any that finish early
+        let mut pending = BTreeMap::new();
+        let mut next_merge = 0;
+        let mut seeds_done = 0;
+        let started = Instant::now();
+        let mut last_progress = started;
+        for outcome in receiver {
+            pending.insert(outcome.index, outcome);
+            while let Some(outcome) = pending.remove(&next_merge) {
+                for (total, chunk) in stats.iter_mut().zip(&outcome.stats) {
+                    total.merge(chunk);
+                }
+                for (row, counts) in head_to_head.iter_mut().zip(&outcome.head_to_head) {
+                    for (count, chunk) in row.iter_mut().zip(counts) {
+                        *count += chunk;
+                    }
+                }
+                if let Some(on_games) = on_games.as_mut() {
+                    on_games(&outcome.games);
+                }
+                seeds_done += outcome.seeds;
+                next_merge += 1;
+            }
+
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                let rate = (seeds_done * strategies.len() as u64) as f64 / started.elapsed().as_secs_f64();
+                eprintln!("Simulated {}/{} seeds ({:.0} games/s)...", seeds_done, total, rate);
             }
         }
+    });
+
+    Matchup {
+        reports: strategies
+            .iter()
+            .zip(&stats)
+            .map(|(&strategy, stats)| stats.report(difficulty, strategy, seeds.start))
+            .collect(),
+        head_to_head,
     }
+}
 
-    // Sort by efficiency (higher is better)
-    resources_to_fix.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap());
-
-    // Simulate fixing resources in order of efficiency
-    let mut sprints = 0;
-    let mut resources_fixed = 0;
-
-    for (waste, savings, fix_sprints, _efficiency, _x, _y) in resources_to_fix.iter() {
-        // Simulate movement cost (average 3 moves per resource)
-        let movement_cost = 20 * 3;
-        game_data.budget -= movement_cost;
-
-        // Simulate reveal cost (1 sprint of burn)
-        sprints += 1;
-        game_data.budget -= game_data.calculate_burn_rate();
-
-        if game_data.budget <= 0 {
-            break;
+fn play_chunk(
+    index: u64,
+    seeds: Range<u64>,
+    difficulty: Difficulty,
+    strategies: &[StrategyKind],
+    config: &Arc<GameConfig>,
+    catalog: &Arc<ResourceCatalog>,
+    keep_games: bool,
+) -> ChunkOutcome {
+    let mut stats = vec![SimulationStats::default(); strategies.len()];
+    let mut head_to_head = vec![vec![0; strategies.len()]; strategies.len()];
+    let mut games = Vec::new();
+
+    for seed in seeds.clone() {
+        let results: Vec<GameResult> = strategies
+            .iter()
+            .map(|&strategy| simulate_game(difficulty, strategy, seed, config, catalog))
+            .collect();
+        for (row, result) in results.iter().enumerate() {
+            stats[row].add(result);
+            for (column, other) in results.iter().enumerate() {
+                if result.won && !other.won {
+                    head_to_head[row][column] += 1;
+                }
+            }
         }
-
-        // Simulate fixing (always 1 sprint now)
-        sprints += 1;
-        game_data.budget -= game_data.calculate_burn_rate();
-
-        if game_data.budget <= 0 {
-            break;
+        if keep_games {
+            games.extend(results);
         }
+    }
 
-        // Resource is fixed
-        game_data.total_waste -= waste;
-
-        // Apply combo multiplier (simplified for simulator)
-        let combo_bonus = if resources_fixed > 0 { 1.2 } else { 1.0 };
-        let effective_savings = (*savings as f32 * combo_bonus) as i32;
-        game_data.monthly_savings += effective_savings;
+    ChunkOutcome {
+        index,
+        seeds: seeds.end - seeds.start,
+        stats,
+        head_to_head,
+        games,
+    }
+}
 
-        // Add adjacency bonus (simplified)
-        if resources_fixed > 0 {
-            game_data.adjacency_bonus += 50;
-        }
+pub fn print_difficulty_summary<'a>(reports: impl IntoIterator<Item = &'a SimulationReport>) {
+    println!("\n🎚️ Win Rate by Difficulty (target {:.0}-{:.0}%):", TARGET_WIN_RATE.0, TARGET_WIN_RATE.1);
+    for report in reports {
+        println!(
+            "  {:<10} {:>5.1}%  {}",
+            report.difficulty.name(),
+            report.win_rate,
+            balance_assessment(report.win_rate)
+        );
+    }
+}
 
-        resources_fixed += 1;
+// Bots compared on the same seeds: how each one did, and how often the row bot won a seed
+// that the column bot lost
+pub fn print_head_to_head(matchup: &Matchup) {
+    let Some(first) = matchup.reports.first() else {
+        return;
+    };
+    println!("\n⚔️ Head-to-Head ({}, {} games each):", first.difficulty.name(), first.total_games);
+    println!("  {:<10} {:>7} {:>14} {:>14}", "Bot", "Win %", "Sprints (win)", "Budget (win)");
+    for report in &matchup.reports {
+        println!(
+            "  {:<10} {:>6.1}% {:>14.1} {:>14.0}",
+            report.strategy.name(),
+            report.win_rate,
+            report.avg_sprints_to_win,
+            report.avg_final_budget_win
+        );
+    }
 
-        // Check win condition
-        game_data.sprint = sprints;
-        if sprints >= 5 {
-            game_data.check_win_condition();
-            if game_data.game_won {
-                break;
+    println!("\n  Seeds won by the row bot and lost by the column bot:");
+    print!("  {:<10}", "");
+    for report in &matchup.reports {
+        print!(" {:>9}", report.strategy.name());
+    }
+    println!();
+    for (row, report) in matchup.reports.iter().enumerate() {
+        print!("  {:<10}", report.strategy.name());
+        for (column, count) in matchup.head_to_head[row].iter().enumerate() {
+            if row == column {
+                print!(" {:>9}", "-");
+            } else {
+                print!(" {:>9}", count);
             }
         }
+        println!();
+    }
+}
+
+// Win rate band the game is balanced for
+pub const TARGET_WIN_RATE: (f32, f32) = (20.0, 30.0);
+
+fn balance_assessment(win_rate: f32) -> &'static str {
+    if win_rate < 10.0 {
+        "⚠️ TOO HARD: Win rate is very low!"
+    } else if win_rate < TARGET_WIN_RATE.0 {
+        "🔴 HARD: Win rate is below target (20-30%)"
+    } else if win_rate <= TARGET_WIN_RATE.1 {
+        "✅ PERFECT: Win rate is in target range (20-30%)"
+    } else if win_rate <= 40.0 {
+        "🟡 SLIGHTLY EASY: Win rate is above target"
+    } else {
+        "⚠️ TOO EASY: Win rate is too high!"
     }
+}
 
-    SimulationResult {
+pub fn simulate_game(
+    difficulty: Difficulty,
+    strategy: StrategyKind,
+    seed: u64,
+    config: &Arc<GameConfig>,
+    catalog: &Arc<ResourceCatalog>,
+) -> GameResult {
+    let settings = config.settings(difficulty);
+    let game_data = GameData::with_config(difficulty, settings.grid_width, settings.grid_height, config.clone());
+    let mut engine = GameEngine::with_catalog(game_data, GameRng::new(seed), catalog.clone());
+    strategy::play(&mut engine, strategy.build(seed).as_mut());
+
+    let game_data = engine.data();
+    GameResult {
+        seed,
+        difficulty,
+        strategy,
         won: game_data.game_won,
-        sprints,
+        victory: game_data.victory,
+        // A bot can stop with budget left, which still counts as a loss
+        loss: if game_data.game_won { None } else { Some(game_data.loss.unwrap_or(LossCause::Stranded)) },
+        sprints: game_data.sprint,
         final_budget: game_data.budget.max(0),
         final_waste: game_data.total_waste,
         final_savings: game_data.monthly_savings,
         waste_percentage: game_data.waste_percentage(),
+        failed_fixes: game_data.failed_fix_attempts,
+        cascade_failures: game_data.cascade_failures.len() as u32,
+        hidden_waste_discovered: game_data.hidden_waste_discovered,
+        bonus_credits: game_data.bonus_credits_earned,
+        realized_savings: game_data.realized_savings,
+        estimate_accuracy: game_data.estimate_accuracy(),
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct SimulationReport {
+    pub difficulty: Difficulty,
+    pub strategy: StrategyKind,
+    pub base_seed: u64,
     pub total_games: u32,
     pub wins: u32,
     pub losses: u32,
     pub win_rate: f32,
+    pub win_rate_interval: (f32, f32),  // 95% Wilson score interval, in percent
     pub avg_sprints_to_win: f32,
     pub avg_sprints_to_lose: f32,
     pub min_sprints_to_win: u32,
     pub max_sprints_to_win: u32,
-    pub avg_final_budget_win: i32,
-    pub avg_final_budget_lose: i32,
+    pub avg_final_budget_win: f32,
+    pub avg_final_budget_lose: f32,
     pub avg_waste_percentage_win: f32,
-    pub avg_savings_win: i32,
+    pub avg_savings_win: f32,
+    pub avg_final_waste: f32,
+    pub wins_by_victory: Vec<(VictoryType, u32)>,
+    pub losses_by_cause: Vec<(LossCause, u32)>,
+    pub avg_failed_fixes: f32,
+    pub avg_cascade_failures: f32,
+    pub avg_hidden_waste_discovered: f32,
+    pub avg_bonus_credits: f32,
+    pub avg_realized_savings: f32,
+    pub avg_estimate_accuracy: f32,
+    pub estimate_accuracy_std_dev: f32,
+    pub min_estimate_accuracy: f32,
+    pub max_estimate_accuracy: f32,
+    // Spread over every game, won or lost
+    pub sprints: Distribution,
+    pub final_budget: Distribution,
+    pub savings: Distribution,
+    pub waste_percentage: Distribution,
+}
+
+// Summary of one histogram, with its buckets for plotting
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Distribution {
+    pub mean: f32,
+    pub std_dev: f32,
+    pub min: f32,
+    pub max: f32,
+    pub p10: f32,
+    pub p50: f32,
+    pub p90: f32,
+    pub bucket_width: f32,
+    pub histogram: Vec<u32>,  // Games per bucket, the first bucket starting at 0
+}
+
+// Fixed-width buckets counted up from 0, so histograms from different chunks of games merge
+// bucket by bucket. Each bucket keeps the range of its values and how many sit on its lowest,
+// so percentiles stay exact where games pile up on one value (every bankrupt game ends on $0)
+// and are within a bucket elsewhere. The mean and spread are exact.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    bucket_width: f64,
+    buckets: Vec<Bucket>,
+    count: u32,
+    sum: f64,
+    sum_squares: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Bucket {
+    count: u32,
+    min: f64,
+    max: f64,
+    at_min: u32,  // Games exactly on `min`
+}
+
+impl Bucket {
+    fn merge(&mut self, other: &Bucket) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        self.count += other.count;
+        self.at_min = match self.min.total_cmp(&other.min) {
+            std::cmp::Ordering::Less => self.at_min,
+            std::cmp::Ordering::Equal => self.at_min + other.at_min,
+            std::cmp::Ordering::Greater => other.at_min,
+        };
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+}
+
+impl Histogram {
+    pub fn new(bucket_width: f64) -> Self {
+        Self {
+            bucket_width,
+            buckets: Vec::new(),
+            count: 0,
+            sum: 0.0,
+            sum_squares: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, value: f64) {
+        let index = (value / self.bucket_width).floor().max(0.0) as usize;
+        if index >= self.buckets.len() {
+            self.buckets.resize(index + 1, Bucket::default());
+        }
+        self.buckets[index].merge(&Bucket { count: 1, min: value, max: value, at_min: 1 });
+        self.count += 1;
+        self.sum += value;
+        self.sum_squares += value * value;
+    }
+
+    pub fn merge(&mut self, other: &Histogram) {
+        if other.buckets.len() > self.buckets.len() {
+            self.buckets.resize(other.buckets.len(), Bucket::default());
+        }
+        for (total, bucket) in self.buckets.iter_mut().zip(&other.buckets) {
+            total.merge(bucket);
+        }
+        self.count += other.count;
+        self.sum += other.sum;
+        self.sum_squares += other.sum_squares;
+    }
+
+    // The value `fraction` of the games fall at or below, spreading each bucket's games above
+    // its lowest value evenly up to its highest
+    pub fn percentile(&self, fraction: f64) -> f64 {
+        let rank = fraction * self.count as f64;
+        let mut below = 0.0;
+        for bucket in self.buckets.iter().filter(|bucket| bucket.count > 0) {
+            if below + bucket.count as f64 >= rank {
+                let above_min = rank - below - bucket.at_min as f64;
+                if above_min <= 0.0 {
+                    return bucket.min;
+                }
+                let spread = (bucket.count - bucket.at_min) as f64;
+                return bucket.min + (bucket.max - bucket.min) * (above_min / spread);
+            }
+            below += bucket.count as f64;
+        }
+        self.buckets.iter().rev().find(|bucket| bucket.count > 0).map_or(0.0, |bucket| bucket.max)
+    }
+
+    pub fn summary(&self) -> Distribution {
+        let occupied = || self.buckets.iter().filter(|bucket| bucket.count > 0);
+        let (Some(first), Some(last)) = (occupied().next(), occupied().next_back()) else {
+            return Distribution {
+                bucket_width: self.bucket_width as f32,
+                ..Default::default()
+            };
+        };
+        let count = self.count as f64;
+        let mean = self.sum / count;
+        let variance = (self.sum_squares / count - mean * mean).max(0.0);
+        Distribution {
+            mean: mean as f32,
+            std_dev: variance.sqrt() as f32,
+            min: first.min as f32,
+            max: last.max as f32,
+            p10: self.percentile(0.1) as f32,
+            p50: self.percentile(0.5) as f32,
+            p90: self.percentile(0.9) as f32,
+            bucket_width: self.bucket_width as f32,
+            histogram: self.buckets.iter().map(|bucket| bucket.count).collect(),
+        }
+    }
+}
+
+// Histograms kept for every game. Bucket widths are fine enough for the presets' budgets
+// and savings without the bucket lists growing long.
+#[derive(Clone, Debug)]
+struct Distributions {
+    sprints: Histogram,
+    final_budget: Histogram,
+    savings: Histogram,
+    waste_percentage: Histogram,
+}
+
+impl Default for Distributions {
+    fn default() -> Self {
+        Self {
+            sprints: Histogram::new(1.0),
+            final_budget: Histogram::new(250.0),
+            savings: Histogram::new(50.0),
+            waste_percentage: Histogram::new(1.0),
+        }
+    }
+}
+
+impl Distributions {
+    fn add(&mut self, result: &GameResult) {
+        self.sprints.add(result.sprints as f64);
+        self.final_budget.add(result.final_budget as f64);
+        self.savings.add(result.final_savings as f64);
+        self.waste_percentage.add(result.waste_percentage as f64);
+    }
+
+    fn merge(&mut self, other: &Distributions) {
+        self.sprints.merge(&other.sprints);
+        self.final_budget.merge(&other.final_budget);
+        self.savings.merge(&other.savings);
+        self.waste_percentage.merge(&other.waste_percentage);
+    }
+}
+
+// Running totals for one bot, cheap to keep per chunk of games and merge
+#[derive(Clone, Debug, Default)]
+pub struct SimulationStats {
+    games: u32,
+    wins: u32,
+    sprints_to_win: u64,
+    sprints_to_lose: u64,
+    min_sprints_to_win: Option<u32>,
+    max_sprints_to_win: u32,
+    final_budget_win: i64,
+    final_budget_lose: i64,
+    waste_percentage_win: f64,
+    savings_win: i64,
+    final_waste: i64,
+    wins_by_victory: [u32; VictoryType::ALL.len()],
+    losses_by_cause: [u32; LossCause::ALL.len()],
+    failed_fixes: u64,
+    cascade_failures: u64,
+    hidden_waste_discovered: i64,
+    bonus_credits: i64,
+    realized_savings: i64,
+    accuracy_games: u32,
+    accuracy_sum: f64,
+    accuracy_sum_squares: f64,
+    accuracy_range: Option<(f32, f32)>,
+    distributions: Distributions,
+}
+
+impl SimulationStats {
+    pub fn add(&mut self, result: &GameResult) {
+        self.games += 1;
+        self.final_waste += result.final_waste as i64;
+        self.failed_fixes += result.failed_fixes as u64;
+        self.cascade_failures += result.cascade_failures as u64;
+        self.hidden_waste_discovered += result.hidden_waste_discovered as i64;
+        self.bonus_credits += result.bonus_credits as i64;
+        self.realized_savings += result.realized_savings as i64;
+        self.distributions.add(result);
+        if let Some(accuracy) = result.estimate_accuracy {
+            self.accuracy_games += 1;
+            self.accuracy_sum += accuracy as f64;
+            self.accuracy_sum_squares += (accuracy as f64).powi(2);
+            self.accuracy_range = Some(match self.accuracy_range {
+                Some((min, max)) => (min.min(accuracy), max.max(accuracy)),
+                None => (accuracy, accuracy),
+            });
+        }
+
+        if result.won {
+            self.wins += 1;
+            self.sprints_to_win += result.sprints as u64;
+            self.min_sprints_to_win = Some(self.min_sprints_to_win.map_or(result.sprints, |min| min.min(result.sprints)));
+            self.max_sprints_to_win = self.max_sprints_to_win.max(result.sprints);
+            self.final_budget_win += result.final_budget as i64;
+            self.waste_percentage_win += result.waste_percentage as f64;
+            self.savings_win += result.final_savings as i64;
+            if let Some(index) = VictoryType::ALL.iter().position(|&v| Some(v) == result.victory) {
+                self.wins_by_victory[index] += 1;
+            }
+        } else {
+            self.sprints_to_lose += result.sprints as u64;
+            self.final_budget_lose += result.final_budget as i64;
+            if let Some(index) = LossCause::ALL.iter().position(|&l| Some(l) == result.loss) {
+                self.losses_by_cause[index] += 1;
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: &SimulationStats) {
+        self.games += other.games;
+        self.wins += other.wins;
+        self.sprints_to_win += other.sprints_to_win;
+        self.sprints_to_lose += other.sprints_to_lose;
+        self.min_sprints_to_win = match (self.min_sprints_to_win, other.min_sprints_to_win) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.max_sprints_to_win = self.max_sprints_to_win.max(other.max_sprints_to_win);
+        self.final_budget_win += other.final_budget_win;
+        self.final_budget_lose += other.final_budget_lose;
+        self.waste_percentage_win += other.waste_percentage_win;
+        self.savings_win += other.savings_win;
+        self.final_waste += other.final_waste;
+        for (total, count) in self.wins_by_victory.iter_mut().zip(other.wins_by_victory) {
+            *total += count;
+        }
+        for (total, count) in self.losses_by_cause.iter_mut().zip(other.losses_by_cause) {
+            *total += count;
+        }
+        self.failed_fixes += other.failed_fixes;
+        self.cascade_failures += other.cascade_failures;
+        self.hidden_waste_discovered += other.hidden_waste_discovered;
+        self.bonus_credits += other.bonus_credits;
+        self.realized_savings += other.realized_savings;
+        self.accuracy_games += other.accuracy_games;
+        self.accuracy_sum += other.accuracy_sum;
+        self.accuracy_sum_squares += other.accuracy_sum_squares;
+        self.accuracy_range = match (self.accuracy_range, other.accuracy_range) {
+            (Some((a_min, a_max)), Some((b_min, b_max))) => Some((a_min.min(b_min), a_max.max(b_max))),
+            (a, b) => a.or(b),
+        };
+        self.distributions.merge(&other.distributions);
+    }
+
+    // Games played from consecutive seeds starting at `base_seed`
+    pub fn report(&self, difficulty: Difficulty, strategy: StrategyKind, base_seed: u64) -> SimulationReport {
+        let losses = self.games - self.wins;
+        let games = self.games.max(1) as f64;
+        let per_win = |total: f64| if self.wins > 0 { total / self.wins as f64 } else { 0.0 };
+        let per_loss = |total: f64| if losses > 0 { total / losses as f64 } else { 0.0 };
+
+        // Spread of how far realized savings landed from the midpoint estimates, per game
+        let (avg_accuracy, accuracy_std_dev) = if self.accuracy_games > 0 {
+            let count = self.accuracy_games as f64;
+            let mean = self.accuracy_sum / count;
+            let variance = (self.accuracy_sum_squares / count - mean * mean).max(0.0);
+            (mean as f32, variance.sqrt() as f32)
+        } else {
+            (0.0, 0.0)
+        };
+        let (min_accuracy, max_accuracy) = self.accuracy_range.unwrap_or((0.0, 0.0));
+
+        SimulationReport {
+            difficulty,
+            strategy,
+            base_seed,
+            total_games: self.games,
+            wins: self.wins,
+            losses,
+            win_rate: (self.wins as f64 / games * 100.0) as f32,
+            win_rate_interval: wilson_interval(self.wins, self.games),
+            avg_sprints_to_win: per_win(self.sprints_to_win as f64) as f32,
+            avg_sprints_to_lose: per_loss(self.sprints_to_lose as f64) as f32,
+            min_sprints_to_win: self.min_sprints_to_win.unwrap_or(0),
+            max_sprints_to_win: self.max_sprints_to_win,
+            avg_final_budget_win: per_win(self.final_budget_win as f64) as f32,
+            avg_final_budget_lose: per_loss(self.final_budget_lose as f64) as f32,
+            avg_waste_percentage_win: per_win(self.waste_percentage_win) as f32,
+            avg_savings_win: per_win(self.savings_win as f64) as f32,
+            avg_final_waste: (self.final_waste as f64 / games) as f32,
+            wins_by_victory: VictoryType::ALL.into_iter().zip(self.wins_by_victory).collect(),
+            losses_by_cause: LossCause::ALL.into_iter().zip(self.losses_by_cause).collect(),
+            avg_failed_fixes: (self.failed_fixes as f64 / games) as f32,
+            avg_cascade_failures: (self.cascade_failures as f64 / games) as f32,
+            avg_hidden_waste_discovered: (self.hidden_waste_discovered as f64 / games) as f32,
+            avg_bonus_credits: (self.bonus_credits as f64 / games) as f32,
+            avg_realized_savings: (self.realized_savings as f64 / games) as f32,
+            avg_estimate_accuracy: avg_accuracy,
+            estimate_accuracy_std_dev: accuracy_std_dev,
+            min_estimate_accuracy: min_accuracy,
+            max_estimate_accuracy: max_accuracy,
+            sprints: self.distributions.sprints.summary(),
+            final_budget: self.distributions.final_budget.summary(),
+            savings: self.distributions.savings.summary(),
+            waste_percentage: self.distributions.waste_percentage.summary(),
+        }
+    }
+}
+
+// 95% confidence interval for a win rate, in percent. Unlike mean ± 1.96σ it stays inside
+// 0-100% and behaves for the near-zero win rates of the harder presets.
+fn wilson_interval(wins: u32, games: u32) -> (f32, f32) {
+    if games == 0 {
+        return (0.0, 0.0);
+    }
+    const Z: f64 = 1.96;
+    let n = games as f64;
+    let p = wins as f64 / n;
+    let denominator = 1.0 + Z * Z / n;
+    let center = (p + Z * Z / (2.0 * n)) / denominator;
+    let half_width = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+    (
+        ((center - half_width).max(0.0) * 100.0) as f32,
+        ((center + half_width).min(1.0) * 100.0) as f32,
+    )
+}
+
+// Histogram squeezed into at most `width` block characters, tallest bucket full height
+fn sparkline(histogram: &[u32], width: usize) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let per_column = histogram.len().div_ceil(width).max(1);
+    let columns: Vec<u32> = histogram.chunks(per_column).map(|chunk| chunk.iter().sum()).collect();
+    let tallest = columns.iter().copied().max().unwrap_or(0).max(1);
+    columns
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                ' '
+            } else {
+                BLOCKS[count as usize * (BLOCKS.len() - 1) / tallest as usize]
+            }
+        })
+        .collect()
 }
 
 impl SimulationReport {
+    pub const CSV_HEADER: &'static str = "difficulty,strategy,base_seed,total_games,wins,losses,win_rate,win_rate_low,win_rate_high,avg_sprints_to_win,avg_sprints_to_lose,min_sprints_to_win,max_sprints_to_win,avg_final_budget_win,avg_final_budget_lose,avg_waste_percentage_win,avg_savings_win,avg_final_waste,elimination_wins,savings_wins,combo_master_wins,balanced_wins,burn_losses,sailing_losses,stranded_losses,avg_failed_fixes,avg_cascade_failures,avg_hidden_waste_discovered,avg_bonus_credits,avg_realized_savings,avg_estimate_accuracy,estimate_accuracy_std_dev,min_estimate_accuracy,max_estimate_accuracy,sprints_p10,sprints_p50,sprints_p90,sprints_std_dev,final_budget_p10,final_budget_p50,final_budget_p90,final_budget_std_dev,savings_p10,savings_p50,savings_p90,savings_std_dev,waste_percentage_p10,waste_percentage_p50,waste_percentage_p90,waste_percentage_std_dev";
+
+    pub fn csv_row(&self) -> String {
+        let victories: Vec<String> = VictoryType::ALL
+            .iter()
+            .map(|victory| {
+                let count = self.wins_by_victory.iter().find(|(v, _)| v == victory).map_or(0, |(_, c)| *c);
+                count.to_string()
+            })
+            .collect();
+        let losses: Vec<String> = LossCause::ALL
+            .iter()
+            .map(|cause| {
+                let count = self.losses_by_cause.iter().find(|(l, _)| l == cause).map_or(0, |(_, c)| *c);
+                count.to_string()
+            })
+            .collect();
+        let distributions: Vec<String> = [&self.sprints, &self.final_budget, &self.savings, &self.waste_percentage]
+            .iter()
+            .map(|d| format!("{:.2},{:.2},{:.2},{:.2}", d.p10, d.p50, d.p90, d.std_dev))
+            .collect();
+        format!(
+            "{},{},{},{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{},{},{:.3},{:.3},{:.2},{:.2},{:.2},{:.4},{:.4},{:.4},{:.4},{}",
+            self.difficulty.name(),
+            self.strategy.name(),
+            self.base_seed,
+            self.total_games,
+            self.wins,
+            self.losses,
+            self.win_rate,
+            self.win_rate_interval.0,
+            self.win_rate_interval.1,
+            self.avg_sprints_to_win,
+            self.avg_sprints_to_lose,
+            self.min_sprints_to_win,
+            self.max_sprints_to_win,
+            self.avg_final_budget_win,
+            self.avg_final_budget_lose,
+            self.avg_waste_percentage_win,
+            self.avg_savings_win,
+            self.avg_final_waste,
+            victories.join(","),
+            losses.join(","),
+            self.avg_failed_fixes,
+            self.avg_cascade_failures,
+            self.avg_hidden_waste_discovered,
+            self.avg_bonus_credits,
+            self.avg_realized_savings,
+            self.avg_estimate_accuracy,
+            self.estimate_accuracy_std_dev,
+            self.min_estimate_accuracy,
+            self.max_estimate_accuracy,
+            distributions.join(","),
+        )
+    }
+
     pub fn print(&self) {
         println!("\n🏴‍☠️ === Pirate FinOps Simulation Report === 🏴‍☠️");
-        println!("\n📊 Overall Statistics:");
-        println!("  Total Games: {}", self.total_games);
+        println!("\n📊 Overall Statistics ({}, {} bot):", self.difficulty.name(), self.strategy.name());
+        match self.base_seed.checked_add(self.total_games as u64) {
+            Some(end) => println!("  Total Games: {} (seeds {}..{})", self.total_games, self.base_seed, end),
+            None => println!("  Total Games: {} (seeds from {})", self.total_games, self.base_seed),
+        }
         println!("  Wins: {} | Losses: {}", self.wins, self.losses);
-        println!("  🎯 Win Rate: {:.1}%", self.win_rate);
+        println!(
+            "  🎯 Win Rate: {:.1}% (95% CI {:.1}-{:.1}%)",
+            self.win_rate, self.win_rate_interval.0, self.win_rate_interval.1
+        );
+
+        if self.wins > 0 {
+            println!("\n🏆 Win Paths:");
+            for (victory, count) in &self.wins_by_victory {
+                let rate = (*count as f32 / self.total_games as f32) * 100.0;
+                println!("  {}: {} ({:.1}% of games)", victory.name(), count, rate);
+            }
+        }
+        if self.losses > 0 {
+            println!("\n☠️ Loss Causes:");
+            for (cause, count) in &self.losses_by_cause {
+                let rate = (*count as f32 / self.total_games as f32) * 100.0;
+                println!("  {}: {} ({:.1}% of games)", cause.name(), count, rate);
+            }
+        }
 
         println!("\n⏱️ Sprint Statistics:");
         if self.wins > 0 {
@@ -230,25 +845,144 @@ impl SimulationReport {
 
         println!("\n💰 Budget Statistics:");
         if self.wins > 0 {
-            println!("  Avg Final Budget (Wins): ${}", self.avg_final_budget_win);
+            println!("  Avg Final Budget (Wins): ${:.0}", self.avg_final_budget_win);
             println!("  Avg Waste % (Wins): {:.1}%", self.avg_waste_percentage_win);
-            println!("  Avg Savings (Wins): ${}", self.avg_savings_win);
+            println!("  Avg Savings (Wins): ${:.0}", self.avg_savings_win);
         }
         if self.losses > 0 {
-            println!("  Avg Final Budget (Losses): ${}", self.avg_final_budget_lose);
+            println!("  Avg Final Budget (Losses): ${:.0}", self.avg_final_budget_lose);
         }
+        println!("  Avg Waste Left: ${:.0}/mo", self.avg_final_waste);
+        println!("  Avg Bonus Credits: ${:.0}", self.avg_bonus_credits);
+        println!("  Avg Realized Savings Banked: ${:.0}", self.avg_realized_savings);
+
+        println!("\n📉 Distributions (all games):");
+        println!("  {:<14} {:>8} {:>8} {:>8} {:>8}  Histogram", "", "p10", "p50", "p90", "Std Dev");
+        for (name, distribution) in [
+            ("Sprints", &self.sprints),
+            ("Final Budget", &self.final_budget),
+            ("Savings/mo", &self.savings),
+            ("Waste %", &self.waste_percentage),
+        ] {
+            println!(
+                "  {:<14} {:>8.1} {:>8.1} {:>8.1} {:>8.1}  {}",
+                name,
+                distribution.p10,
+                distribution.p50,
+                distribution.p90,
+                distribution.std_dev,
+                sparkline(&distribution.histogram, 32)
+            );
+        }
+
+        println!("\n💥 Risk Statistics:");
+        println!("  Avg Failed Fixes: {:.2}", self.avg_failed_fixes);
+        println!("  Avg Cascade Outages: {:.2}", self.avg_cascade_failures);
+        println!("  Avg Hidden Waste Uncovered: ${:.0}/mo", self.avg_hidden_waste_discovered);
+
+        println!("\n🎲 Savings Estimate Accuracy (actual vs midpoint, per game):");
+        println!("  Avg: {:.1}% | Std Dev: {:.1}%", self.avg_estimate_accuracy * 100.0, self.estimate_accuracy_std_dev * 100.0);
+        println!("  Range: {:.1}% - {:.1}%", self.min_estimate_accuracy * 100.0, self.max_estimate_accuracy * 100.0);
 
         println!("\n📈 Balance Assessment:");
-        if self.win_rate < 10.0 {
-            println!("  ⚠️ TOO HARD: Win rate is very low!");
-        } else if self.win_rate < 20.0 {
-            println!("  🔴 HARD: Win rate is below target (20-30%)");
-        } else if self.win_rate <= 30.0 {
-            println!("  ✅ PERFECT: Win rate is in target range (20-30%)");
-        } else if self.win_rate <= 40.0 {
-            println!("  🟡 SLIGHTLY EASY: Win rate is above target");
-        } else {
-            println!("  ⚠️ TOO EASY: Win rate is too high!");
+        println!("  {}", balance_assessment(self.win_rate));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(bucket_width: f64, values: &[f64]) -> Histogram {
+        let mut histogram = Histogram::new(bucket_width);
+        for &value in values {
+            histogram.add(value);
         }
+        histogram
     }
-}
\ No newline at end of file
+
+    fn game(seed: u64) -> GameResult {
+        let won = seed.is_multiple_of(3);
+        GameResult {
+            seed,
+            difficulty: Difficulty::Normal,
+            strategy: StrategyKind::Greedy,
+            won,
+            victory: won.then_some(VictoryType::ALL[seed as usize % VictoryType::ALL.len()]),
+            loss: (!won).then_some(LossCause::ALL[seed as usize % LossCause::ALL.len()]),
+            sprints: 5 + (seed as u32 * 7) % 20,
+            final_budget: if won { 1000 + seed as i32 * 250 } else { 0 },
+            final_waste: 300 + seed as i32 * 10,
+            final_savings: seed as i32 * 50,
+            waste_percentage: 12.5 + seed as f32,
+            failed_fixes: seed as u32 % 2,
+            cascade_failures: seed as u32 % 4,
+            hidden_waste_discovered: seed as i32 * 20,
+            bonus_credits: 100,
+            realized_savings: seed as i32 * 75,
+            estimate_accuracy: seed.is_multiple_of(2).then_some(0.75 + seed as f32 / 8.0),
+        }
+    }
+
+    fn stats(games: impl IntoIterator<Item = GameResult>) -> SimulationStats {
+        let mut stats = SimulationStats::default();
+        for game in games {
+            stats.add(&game);
+        }
+        stats
+    }
+
+    #[test]
+    fn percentiles_of_a_small_sample() {
+        let histogram = sample(1.0, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
+        assert_eq!(histogram.percentile(0.1), 1.0);
+        assert_eq!(histogram.percentile(0.5), 5.0);
+        assert_eq!(histogram.percentile(0.9), 9.0);
+
+        // Within a wide bucket, games above its lowest value are spread evenly up to its highest
+        let histogram = sample(10.0, &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        assert_eq!(histogram.percentile(0.5), 4.0);
+    }
+
+    #[test]
+    fn percentiles_stay_exact_where_games_pile_up() {
+        let histogram = sample(250.0, &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1000.0, 2000.0, 3000.0]);
+        assert_eq!(histogram.percentile(0.5), 0.0);
+        assert_eq!(histogram.percentile(0.9), 2000.0);
+    }
+
+    #[test]
+    fn summary_mean_and_std_dev() {
+        let summary = sample(1.0, &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).summary();
+        assert_eq!(summary.mean, 5.0);
+        assert_eq!(summary.std_dev, 2.0);
+        assert_eq!((summary.min, summary.max), (2.0, 9.0));
+        assert_eq!(summary.histogram, vec![0, 0, 1, 0, 3, 2, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn wilson_interval_at_the_extremes() {
+        // With no wins the upper bound is z² / (n + z²)
+        let upper = (1.96f64 * 1.96 / (10.0 + 1.96 * 1.96) * 100.0) as f32;
+        let (low, high) = wilson_interval(0, 10);
+        assert_eq!(low, 0.0);
+        assert!((high - upper).abs() < 1e-3);
+
+        let (low, high) = wilson_interval(10, 10);
+        assert!((low - (100.0 - upper)).abs() < 1e-3);
+        assert_eq!(high, 100.0);
+
+        assert_eq!(wilson_interval(0, 0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn merged_stats_match_stats_of_all_games() {
+        let (a, b): (Vec<GameResult>, Vec<GameResult>) = (0..40).map(game).partition(|game| game.seed < 17);
+        let mut merged = stats(a.clone());
+        merged.merge(&stats(b.clone()));
+        let whole = stats(a.into_iter().chain(b));
+
+        let report = |stats: &SimulationStats| format!("{:?}", stats.report(Difficulty::Normal, StrategyKind::Greedy, 0));
+        assert_eq!(report(&merged), report(&whole));
+    }
+}
@@ -0,0 +1,676 @@
+use serde::Serialize;
+use crate::engine::{Action, GameEngine, Heading};
+use crate::resources::{Difficulty, GameData};
+use crate::strategy::{sail_and_fix, MAX_FIX_ATTEMPTS};
+use crate::win_conditions::VictoryType;
+
+// Perfect-information solver for one seed. It sees every tile, and since every roll comes from
+// the seeded RNG it effectively knows the dice too, so a plan replays exactly. The plans it
+// weighs are every order of fixes (sail the cheapest route to a resource, fix it, retry until it
+// sticks, as the bots do), stopping after any of them to wait for the savings to come in.
+//
+// The search is depth first, most valuable fixes first, and drops a branch only when a bound
+// proves nothing in it can beat the best plan so far: the least waste the resources still on
+// the bill can shrink to, the fewest sprints needed to fix enough of them, and the most budget
+// the savings could bring by then. A search the node limit doesn't cut short has found the
+// optimum among those plans. It runs a slice at a time, for callers that can't block on it.
+
+// Game states a par search explores before settling for the best plan so far. Both the
+// game-over screen and `simulate --solve` use it, so a seed's par reads the same in either.
+pub const PAR_NODE_LIMIT: u64 = 50_000;
+// Sprints waited out after the last fix, in case incoming savings tip a win
+const MAX_WAIT_SPRINTS: u32 = 24;
+// Budget spent sailing is weighed against a fix's value per sprint at this rate when ordering
+const TRAVEL_WEIGHT: f32 = 0.5;
+
+// What counts as the best plan
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum SolveGoal {
+    Fastest,  // Win in the fewest sprints, then with the most budget
+    Richest,  // Win with the most budget, then in the fewest sprints
+}
+
+impl SolveGoal {
+    pub const ALL: [SolveGoal; 2] = [Self::Fastest, Self::Richest];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Fastest => "fastest",
+            Self::Richest => "richest",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|g| g.name().eq_ignore_ascii_case(name))
+    }
+}
+
+// One fix in the plan: the route sailed to it, and where the game stood once it stuck
+#[derive(Clone, Debug, Serialize)]
+pub struct PlanStep {
+    pub x: u8,
+    pub y: u8,
+    pub resource: String,
+    pub route: Vec<Heading>,
+    pub attempts: u32,  // Fixes started, failures included
+    pub sprint: u32,
+    pub budget: i32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Solution {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub goal: SolveGoal,
+    pub won: bool,
+    pub victory: Option<VictoryType>,
+    pub sprints: u32,
+    pub budget: i32,
+    pub plan: Vec<PlanStep>,
+    pub waited_sprints: u32,  // Sprints waited after the last fix
+    pub nodes: u64,
+    pub node_limit: u64,
+    pub optimal: bool,  // The search ran to the end; false when the node limit cut it short
+}
+
+impl Solution {
+    // Short form for the game-over screen. A par the node limit cut short says so.
+    pub fn par(&self) -> String {
+        let outcome = match (self.won, self.optimal) {
+            (true, _) => format!("won in {} sprints with ${}", self.sprints, self.budget),
+            (false, true) => "no winning plan".to_string(),
+            (false, false) => "no win found".to_string(),
+        };
+        if self.optimal {
+            format!("Par: {}", outcome)
+        } else {
+            format!("Par (best in {} states): {}", self.node_limit, outcome)
+        }
+    }
+
+    // Plays the plan move by move on `engine`, which should stand where the solver started.
+    // The dice come from the engine's seed, so the result lands on `sprints` and `budget`.
+    pub fn replay(&self, engine: &GameEngine) -> GameEngine {
+        let mut engine = engine.clone();
+        for step in &self.plan {
+            for &heading in &step.route {
+                engine.apply(Action::Move(heading));
+            }
+            for _ in 0..step.attempts {
+                engine.apply(Action::Fix);
+                while !engine.is_over() && engine.tile(step.x, step.y).fixing_sprints_left > 0 {
+                    engine.apply(Action::AdvanceSprint);
+                }
+            }
+        }
+        for _ in 0..self.waited_sprints {
+            engine.apply(Action::AdvanceSprint);
+        }
+        engine
+    }
+}
+
+pub struct Solver {
+    pub goal: SolveGoal,
+    pub node_limit: u64,  // Engine states explored before settling for the best plan so far
+}
+
+impl Solver {
+    // Solves from wherever `engine` stands; pass `engine.restart()` for the seed's par
+    pub fn solve(&self, engine: &GameEngine) -> Solution {
+        self.start(engine).run(u64::MAX).expect("an unbounded run finishes the search")
+    }
+
+    // A search that hasn't explored anything yet, for callers that run it a slice at a time
+    pub fn start(&self, engine: &GameEngine) -> SolveRun {
+        SolveRun {
+            goal: self.goal,
+            node_limit: self.node_limit,
+            seed: engine.seed(),
+            root: Some(engine.clone()),
+            nodes: 0,
+            cut_short: false,
+            stack: Vec::new(),
+            plan: Vec::new(),
+            best: None,
+        }
+    }
+}
+
+// A finished line of play
+struct Ending {
+    engine: GameEngine,
+    plan: Vec<PlanStep>,
+    waited_sprints: u32,
+}
+
+// A state whose fixes are still being tried, most valuable first
+struct Frame {
+    engine: GameEngine,
+    targets: Vec<(u8, u8)>,
+    next: usize,
+    bound: Bound,
+}
+
+// The best any ending reachable from a state could do
+#[derive(Clone, Copy)]
+struct Bound {
+    sprint: u32,  // Earliest sprint a win could come
+    budget: i32,  // Most budget a win could be left with
+    waiting_can_win: bool,  // Little enough waste is left that a win needs no more fixes
+}
+
+// A search in progress. Every state whose frame is on the stack has a step in `plan`, bar the
+// one it started from.
+pub struct SolveRun {
+    goal: SolveGoal,
+    node_limit: u64,
+    seed: u64,
+    root: Option<GameEngine>,  // Explored on the first run
+    nodes: u64,
+    cut_short: bool,
+    stack: Vec<Frame>,
+    plan: Vec<PlanStep>,
+    best: Option<Ending>,
+}
+
+impl SolveRun {
+    // Explores about `nodes` more states; the solution once the search is over
+    pub fn run(&mut self, nodes: u64) -> Option<Solution> {
+        let stop = self.nodes.saturating_add(nodes);
+        if let Some(root) = self.root.take() {
+            self.explore(&root);
+        }
+        while self.nodes < stop && !self.stack.is_empty() {
+            self.step();
+        }
+        if self.stack.is_empty() {
+            Some(self.solution())
+        } else {
+            None
+        }
+    }
+
+    fn solution(&self) -> Solution {
+        let best = self.best.as_ref().expect("every search reaches at least one ending");
+        let data = best.engine.data();
+        Solution {
+            seed: self.seed,
+            difficulty: data.difficulty,
+            goal: self.goal,
+            won: data.game_won,
+            victory: data.victory,
+            sprints: data.sprint,
+            budget: data.budget,
+            plan: best.plan.clone(),
+            waited_sprints: best.waited_sprints,
+            nodes: self.nodes,
+            node_limit: self.node_limit,
+            optimal: !self.cut_short,
+        }
+    }
+
+    // Tries the top frame's next fix, or drops the frame once none is left worth trying
+    fn step(&mut self) {
+        let depth = self.stack.len() - 1;
+        let frame = &self.stack[depth];
+        let target = frame.targets.get(frame.next).copied();
+        // The best plan may have improved since the bound was taken
+        let (Some((x, y)), false) = (target, self.cannot_beat(Some(&frame.bound))) else {
+            self.stack.pop();
+            self.plan.truncate(depth.saturating_sub(1));
+            return;
+        };
+        self.stack[depth].next += 1;
+
+        let engine = &self.stack[depth].engine;
+        let mut next = engine.clone();
+        let route = engine.find_path(x, y).unwrap_or_default();
+        let arrived = sail_and_fix(&mut next, x, y);
+        let step = PlanStep {
+            x,
+            y,
+            resource: engine.catalog().kind(engine.resource_at(x, y)).name.clone(),
+            route,
+            attempts: attempts(engine, &next, x, y),
+            sprint: next.data().sprint,
+            budget: next.data().budget,
+        };
+        self.plan.push(step);
+        if arrived {
+            self.explore(&next);
+        } else if next.is_over() {
+            self.finish(&next, 0);
+        }
+
+        if self.cut_short {
+            // Once the limit is hit, every frame still on the stack stops too
+            self.stack.clear();
+            self.plan.clear();
+        } else if self.stack.len() == depth + 1 {
+            self.plan.pop();
+        }
+    }
+
+    // Takes stock of a state, and pushes a frame for it if fixing on could still beat the best
+    fn explore(&mut self, engine: &GameEngine) {
+        // Checked before the bound and targets, which cost a full sailing-cost search. Waiting out
+        // a state that is already over just finishes it.
+        if self.nodes == self.node_limit {
+            self.cut_short = true;
+            self.wait_out(engine);
+            return;
+        }
+        self.nodes += 1;
+        if engine.is_over() {
+            self.finish(engine, 0);
+            return;
+        }
+        let costs = engine.sailing_costs();
+        let bound = bound(engine, &costs);
+        if self.cannot_beat(bound.as_ref()) {
+            return;
+        }
+        if self.best.is_none() || bound.is_some_and(|bound| bound.waiting_can_win) {
+            self.wait_out(engine);
+        }
+        let Some(bound) = bound else {
+            return;
+        };
+        let targets = targets(engine, &costs);
+        if !targets.is_empty() {
+            self.stack.push(Frame { engine: engine.clone(), targets, next: 0, bound });
+        }
+    }
+
+    // Stops fixing: sit tight and let the savings come in
+    fn wait_out(&mut self, engine: &GameEngine) {
+        let mut engine = engine.clone();
+        let mut waited = 0;
+        while !engine.is_over() && waited < MAX_WAIT_SPRINTS {
+            engine.apply(Action::AdvanceSprint);
+            waited += 1;
+        }
+        self.finish(&engine, waited);
+    }
+
+    fn finish(&mut self, engine: &GameEngine, waited_sprints: u32) {
+        let better = match &self.best {
+            Some(best) => self.better(engine.data(), best.engine.data()),
+            None => true,
+        };
+        if better {
+            self.best = Some(Ending {
+                engine: engine.clone(),
+                plan: self.plan.clone(),
+                waited_sprints,
+            });
+        }
+    }
+
+    fn better(&self, a: &GameData, b: &GameData) -> bool {
+        if a.game_won != b.game_won {
+            return a.game_won;
+        }
+        if !a.game_won {
+            // Of two losses, the one that got further: more savings, less waste left
+            return a.monthly_savings - a.total_waste > b.monthly_savings - b.total_waste;
+        }
+        match self.goal {
+            SolveGoal::Fastest => (a.sprint, -a.budget) < (b.sprint, -b.budget),
+            SolveGoal::Richest => (-a.budget, a.sprint) < (-b.budget, b.sprint),
+        }
+    }
+
+    // Whether no ending within `bound` (None: no win at all) can beat the best so far. Ties
+    // can't either, since `better` wants strictly ahead.
+    fn cannot_beat(&self, bound: Option<&Bound>) -> bool {
+        let Some(best) = &self.best else {
+            return false;
+        };
+        let best = best.engine.data();
+        match bound {
+            None => true,
+            Some(_) if !best.game_won => false,
+            Some(bound) => match self.goal {
+                SolveGoal::Fastest => (bound.sprint, -bound.budget) >= (best.sprint, -best.budget),
+                SolveGoal::Richest => (-bound.budget, bound.sprint) >= (-best.budget, best.sprint),
+            },
+        }
+    }
+}
+
+// Bounds every ending reachable from `engine` by fixing its reachable resources. None when no
+// plan from here can win.
+fn bound(engine: &GameEngine, costs: &[Vec<Option<i32>>]) -> Option<Bound> {
+    let data = engine.data();
+    let config = &data.config;
+    let sprint = data.sprint;
+
+    // Waste out of the ship's reach stays on the bill whatever the plan
+    let mut stranded = 0.0;
+    let mut fixable: Vec<(f32, u32)> = Vec::new();  // (least waste, sprints to fix)
+    let mut savings = 0i64;
+    let mut bonus = 0i64;
+    for x in 0..engine.grid_width() {
+        for y in 0..engine.grid_height() {
+            let resource = engine.resource_at(x, y);
+            let tile = engine.tile(x, y);
+            if resource.is_empty() || tile.fixed {
+                continue;
+            }
+            let least = least_waste(engine, x, y);
+            if costs[x as usize][y as usize].is_none() {
+                stranded += least;
+                continue;
+            }
+            let kind = engine.catalog().kind(resource);
+            let sprints = if tile.fixing_sprints_left > 0 { tile.fixing_sprints_left as u32 } else { kind.fix_sprints as u32 };
+            fixable.push((least, sprints));
+            savings += (kind.savings_range.1 as f32 * config.combo_cap) as i64;
+            bonus += kind.bonus_credit.max(0) as i64;
+        }
+    }
+
+    // The fewest fix sprints that take enough waste off: a fractional knapsack by waste per sprint
+    let ceiling = waste_ceiling(data);
+    let mut excess = stranded + fixable.iter().map(|&(least, _)| least).sum::<f32>() - ceiling;
+    let waiting_can_win = excess <= 0.0;
+    fixable.sort_by(|a, b| (b.0 / b.1 as f32).total_cmp(&(a.0 / a.1 as f32)));
+    let mut fix_sprints = 0.0;
+    for &(least, sprints) in fixable.iter().filter(|&&(least, _)| least > 0.0) {
+        if excess <= 0.0 {
+            break;
+        }
+        fix_sprints += (excess / least).min(1.0) * sprints as f32;
+        excess -= least;
+    }
+    if excess > 0.0 {
+        return None;
+    }
+    // Wins are only checked as a sprint ends
+    let earliest = (sprint + 1)
+        .max(config.min_sprints_to_win)
+        .max(sprint + (fix_sprints - 1e-3).ceil() as u32);
+
+    // The most budget by then: every fix at the top of its savings range at the full combo,
+    // paying out from the first month close any fix could reach, with nothing spent but the base burn
+    let credits_from = fixable.iter().map(|&(_, sprints)| sprint + sprints + config.realization_lag_sprints).min();
+    let latest = sprint + fixable.iter().map(|&(_, sprints)| sprints).sum::<u32>() * MAX_FIX_ATTEMPTS + MAX_WAIT_SPRINTS;
+    let mut budget = data.budget as i64 + bonus;
+    let mut richest: Option<i64> = None;
+    for t in sprint + 1..=latest {
+        if t.is_multiple_of(config.sprints_per_month) {
+            budget += data.monthly_savings.max(0) as i64;
+            if credits_from.is_some_and(|from| t >= from) {
+                budget += savings;
+            }
+        }
+        budget -= config.base_burn as i64;
+        if t >= earliest {
+            richest = richest.max(Some(budget));
+        }
+    }
+
+    richest.map(|budget| Bound {
+        sprint: earliest,
+        budget: budget.min(i32::MAX as i64) as i32,
+        waiting_can_win,
+    })
+}
+
+// The most total waste any enabled victory path lets through. The percentage paths measure
+// waste against a burn that grows with it, so their ceiling is where the two meet.
+fn waste_ceiling(data: &GameData) -> f32 {
+    let victory = &data.config.victory;
+    let share_ceiling = |percentage: f32| {
+        let share = percentage / 100.0;
+        let rest = 1.0 - share * data.settings.burn_coefficient;
+        // With no base burn a small enough waste burns nothing, which reads as 0%
+        if rest <= 0.0 || data.config.base_burn <= 0 {
+            f32::INFINITY
+        } else {
+            share * data.config.base_burn as f32 / rest
+        }
+    };
+    let ceiling = data
+        .settings
+        .win_conditions
+        .iter()
+        .map(|victory_type| match victory_type {
+            VictoryType::Elimination => victory.elimination_max_waste as f32,
+            VictoryType::Savings => share_ceiling(victory.savings_max_waste_percentage),
+            VictoryType::ComboMaster => victory.combo_max_waste as f32,
+            VictoryType::Balanced => share_ceiling(victory.balanced_max_waste_percentage),
+        })
+        .fold(0.0, f32::max);
+    // Slack for the float rounding in the engine's own percentage
+    ceiling + 1.0
+}
+
+// The least the resource at (x, y) can add to the waste from the next sprint on: decay at its
+// lowest, no outage, and each neighbor not yet fixed at the weaker of its two pulls
+fn least_waste(engine: &GameEngine, x: u8, y: u8) -> f32 {
+    let resource = engine.resource_at(x, y);
+    let catalog = engine.catalog();
+    let mut neighborhood = 1.0;
+    for cell in catalog.kernel() {
+        let (nx, ny) = (x as i32 + cell.dx, y as i32 + cell.dy);
+        if nx < 0 || ny < 0 || nx >= engine.grid_width() as i32 || ny >= engine.grid_height() as i32 {
+            continue;
+        }
+        let neighbor = engine.resource_at(nx as u8, ny as u8);
+        if neighbor.is_empty() {
+            continue;
+        }
+        let fixed = catalog.neighbor_factor(resource, neighbor, true, cell.weight);
+        let factor = if engine.tile(nx as u8, ny as u8).fixed {
+            fixed
+        } else {
+            fixed.min(catalog.neighbor_factor(resource, neighbor, false, cell.weight))
+        };
+        if factor <= 0.0 {
+            return 0.0;
+        }
+        neighborhood *= factor;
+    }
+    let outage = engine.data().config.outage_multiplier.clamp(0.0, 1.0);
+    let waste = catalog.kind(resource).waste_cost as f32
+        * engine.decay_floor(resource, engine.data().sprint)
+        * neighborhood
+        * outage;
+    // Shaved a hair so float rounding can't lift it over what the engine truncates to
+    (waste * 0.9999).floor()
+}
+
+// Fixes started at (x, y) between `before` and `after`: every failure, plus the one that stuck
+// or was still running when the game ended
+fn attempts(before: &GameEngine, after: &GameEngine, x: u8, y: u8) -> u32 {
+    let tile = after.tile(x, y);
+    let last = tile.fixed || tile.fixing_sprints_left > 0;
+    after.data().failed_fix_attempts - before.data().failed_fix_attempts + last as u32
+}
+
+// Every resource the ship can reach and fix, fog or not, most valuable first. Hidden resources
+// wait until a fix uncovers them, as they would for a player.
+fn targets(engine: &GameEngine, costs: &[Vec<Option<i32>>]) -> Vec<(u8, u8)> {
+    let mut targets: Vec<((u8, u8), f32)> = Vec::new();
+    for x in 0..engine.grid_width() {
+        for y in 0..engine.grid_height() {
+            let resource = engine.resource_at(x, y);
+            let tile = engine.tile(x, y);
+            let Some(cost) = costs[x as usize][y as usize] else {
+                continue;
+            };
+            if resource.is_empty() || tile.hidden || tile.fixed || tile.fixing_sprints_left > 0 {
+                continue;
+            }
+            let kind = engine.catalog().kind(resource);
+            let (min_savings, max_savings) = kind.savings_range;
+            let value = kind.waste_cost as f32 * tile.decay_multiplier + (min_savings + max_savings) as f32 / 2.0;
+            targets.push(((x, y), value / kind.fix_sprints as f32 - cost as f32 * TRAVEL_WEIGHT));
+        }
+    }
+    targets.sort_by(|a, b| b.1.total_cmp(&a.1));
+    targets.into_iter().map(|(target, _)| target).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use super::*;
+    use crate::catalog::ResourceCatalog;
+    use crate::components::{ResourceType, Terrain};
+    use crate::config::GameConfig;
+    use crate::resources::GameRng;
+    use crate::win_conditions::VictorySet;
+
+    // Open 5x5 sea with the ship in the middle and three resources that can't fail or hide
+    fn small_map() -> GameEngine {
+        let catalog = ResourceCatalog::builtin_shared();
+        let mut config = GameConfig::builtin();
+        config.hidden_chance = 0.0;
+        let data = GameData::with_config(Difficulty::Normal, 5, 5, Arc::new(config));
+        let mut grid = vec![vec![ResourceType::default(); 5]; 5];
+        for (id, x, y) in [("IdleVM", 0, 0), ("ExpiredBackups", 4, 1), ("OrphanedDisk", 1, 4)] {
+            grid[x][y] = catalog.find(id).unwrap();
+        }
+        let terrain = vec![vec![Terrain::OpenWater; 5]; 5];
+        GameEngine::with_grid(data, grid, terrain, GameRng::new(7), catalog)
+    }
+
+    fn solve(goal: SolveGoal, node_limit: u64, engine: &GameEngine) -> Solution {
+        Solver { goal, node_limit }.solve(engine)
+    }
+
+    // Waits out the sprints the solver would, from wherever `engine` stands
+    fn wait(mut engine: GameEngine) -> GameEngine {
+        for _ in 0..MAX_WAIT_SPRINTS {
+            if engine.is_over() {
+                break;
+            }
+            engine.apply(Action::AdvanceSprint);
+        }
+        engine
+    }
+
+    #[test]
+    fn matches_every_plan_tried_by_hand() {
+        let start = small_map();
+        let targets = [(0, 0), (4, 1), (1, 4)];
+        let orders = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+        for goal in SolveGoal::ALL {
+            let mut best: Option<(u32, i32)> = None;
+            // Each order, stopping to wait after any number of its fixes
+            for order in orders {
+                for fixes in 0..=order.len() {
+                    let mut engine = start.clone();
+                    for &index in &order[..fixes] {
+                        let (x, y) = targets[index];
+                        if !engine.is_over() {
+                            sail_and_fix(&mut engine, x, y);
+                        }
+                    }
+                    let engine = wait(engine);
+                    let data = engine.data();
+                    let key = |sprint: u32, budget: i32| match goal {
+                        SolveGoal::Fastest => (sprint as i64, -budget as i64),
+                        SolveGoal::Richest => (-budget as i64, sprint as i64),
+                    };
+                    if data.game_won && best.is_none_or(|(sprint, budget)| key(data.sprint, data.budget) < key(sprint, budget)) {
+                        best = Some((data.sprint, data.budget));
+                    }
+                }
+            }
+
+            let solution = solve(goal, 10_000, &start);
+            assert!(solution.optimal, "{}", goal.name());
+            assert!(solution.won, "{}", goal.name());
+            assert_eq!(Some((solution.sprints, solution.budget)), best, "{}", goal.name());
+        }
+    }
+
+    #[test]
+    fn no_winning_plan_is_proven_without_a_search() {
+        // Waste nobody could ever sail to keeps every victory out of reach
+        let mut config = GameConfig::builtin();
+        config.victory.elimination_max_waste = 0;
+        let mut data = GameData::with_config(Difficulty::Normal, 5, 5, Arc::new(config));
+        data.settings.win_conditions = VictorySet::NONE.with(VictoryType::Elimination);
+        let catalog = ResourceCatalog::builtin_shared();
+        let mut grid = vec![vec![ResourceType::default(); 5]; 5];
+        grid[4][4] = catalog.find("IdleVM").unwrap();
+        let mut terrain = vec![vec![Terrain::OpenWater; 5]; 5];
+        terrain[4][3] = Terrain::Reef;
+        terrain[3][4] = Terrain::Reef;
+        let engine = GameEngine::with_grid(data, grid, terrain, GameRng::new(7), catalog);
+
+        let solution = solve(SolveGoal::Fastest, 10_000, &engine);
+        assert!(solution.optimal);
+        assert!(!solution.won);
+        assert!(solution.plan.is_empty());
+        assert_eq!(solution.nodes, 1);
+        assert_eq!(solution.par(), "Par: no winning plan");
+    }
+
+    #[test]
+    fn a_run_in_slices_matches_one_in_a_go() {
+        let data = GameData::with_config(Difficulty::Normal, 6, 6, Arc::new(GameConfig::builtin()));
+        let engine = GameEngine::new(data, GameRng::new(3));
+        let solver = Solver { goal: SolveGoal::Fastest, node_limit: 2_000 };
+        let whole = solver.solve(&engine);
+        let mut run = solver.start(&engine);
+        let mut slices = 1;
+        let sliced = loop {
+            match run.run(50) {
+                Some(solution) => break solution,
+                None => slices += 1,
+            }
+        };
+        assert!(slices > 1, "the search fit in one slice");
+        assert_eq!((sliced.sprints, sliced.budget, sliced.nodes), (whole.sprints, whole.budget, whole.nodes));
+        assert_eq!(sliced.plan.len(), whole.plan.len());
+    }
+
+    #[test]
+    fn replaying_the_plan_lands_on_the_par() {
+        let mut seeds = 0;
+        for seed in 0..4 {
+            let data = GameData::with_config(Difficulty::Normal, 6, 6, Arc::new(GameConfig::builtin()));
+            let engine = GameEngine::new(data, GameRng::new(seed));
+            let solution = solve(SolveGoal::Fastest, 2_000, &engine);
+            let replayed = solution.replay(&engine.restart());
+            assert_eq!(replayed.data().sprint, solution.sprints, "seed {}", seed);
+            assert_eq!(replayed.data().budget, solution.budget, "seed {}", seed);
+            assert_eq!(replayed.data().game_won, solution.won, "seed {}", seed);
+            seeds += solution.plan.iter().any(|step| !step.route.is_empty()) as u32;
+        }
+        assert!(seeds > 0, "no plan sailed anywhere");
+    }
+
+    #[test]
+    fn fastest_and_richest_trade_sprints_for_budget() {
+        let mut differed = false;
+        for seed in 0..16 {
+            let data = GameData::with_config(Difficulty::Normal, 5, 5, Arc::new(GameConfig::builtin()));
+            let engine = GameEngine::new(data, GameRng::new(seed));
+            let fastest = solve(SolveGoal::Fastest, 20_000, &engine);
+            let richest = solve(SolveGoal::Richest, 20_000, &engine);
+            if !(fastest.won && richest.won && fastest.optimal && richest.optimal) {
+                continue;
+            }
+            assert!(fastest.sprints <= richest.sprints, "seed {}", seed);
+            assert!(richest.budget >= fastest.budget, "seed {}", seed);
+            differed |= (fastest.sprints, fastest.budget) != (richest.sprints, richest.budget);
+        }
+        assert!(differed, "the goals never chose differently");
+    }
+
+    #[test]
+    fn hitting_the_node_limit_is_reported() {
+        let solution = solve(SolveGoal::Fastest, 1, &small_map());
+        assert!(!solution.optimal);
+        assert_eq!(solution.nodes, 1);
+        assert!(solution.par().starts_with("Par (best in 1 states): "));
+    }
+}
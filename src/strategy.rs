@@ -0,0 +1,705 @@
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::Serialize;
+use crate::catalog::{ResourceCatalog, ResourceKind};
+use crate::components::{ResourceType, Terrain};
+use crate::engine::{Action, GameEngine};
+use crate::resources::{resource_count, GameData, GameRng};
+
+// Bots that play the game for the simulator. A bot only sees what a player would: the charted
+// terrain, the HUD numbers and the tiles its scans have revealed. The engine does the rest.
+
+// Fixes started on one target before the bot gives up on it. Without a cap a fix that always
+// fails, in a game that burns nothing, would be retried forever.
+pub const MAX_FIX_ATTEMPTS: u32 = 16;
+// Budget spent sailing is weighed against a fix's monthly value per sprint of work at this rate
+const TRAVEL_WEIGHT: f32 = 0.5;
+// Extra weight per known cardinal neighbor for the Cluster bot (fixed neighbors count twice)
+const CLUSTER_WEIGHT: f32 = 0.25;
+// How much more the Combo bot values a fix that chains off the last one
+const CHAIN_WEIGHT: f32 = 2.0;
+// Moves the Search bot weighs at each decision (the best fixes in sight, plus scouting), and
+// how many imagined maps it plays each one out on
+const SEARCH_CANDIDATES: usize = 4;
+const SEARCH_SAMPLES: usize = 8;
+// A won playout outscores any lost one, whatever the budget left
+const SEARCH_WIN_SCORE: f32 = 1_000_000.0;
+// Mixed into the game seed for the bots' own RNG, so they don't replay the map generator's draws
+const BOT_STREAM: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// What a bot may look at. Unrevealed tiles are fog and hidden resources look like clear water.
+pub struct PlayerView<'a> {
+    engine: &'a GameEngine,
+    sailing_costs: Vec<Vec<Option<i32>>>,
+}
+
+impl<'a> PlayerView<'a> {
+    pub fn new(engine: &'a GameEngine) -> Self {
+        Self {
+            engine,
+            sailing_costs: engine.sailing_costs(),
+        }
+    }
+
+    pub fn data(&self) -> &GameData {
+        self.engine.data()
+    }
+
+    pub fn catalog(&self) -> &ResourceCatalog {
+        self.engine.catalog()
+    }
+
+    pub fn grid_width(&self) -> u8 {
+        self.engine.grid_width()
+    }
+
+    pub fn grid_height(&self) -> u8 {
+        self.engine.grid_height()
+    }
+
+    pub fn position(&self) -> (u8, u8) {
+        (self.data().player_x, self.data().player_y)
+    }
+
+    // Terrain is charted, so it's visible through the fog
+    pub fn terrain_at(&self, x: u8, y: u8) -> Terrain {
+        self.engine.terrain_at(x, y)
+    }
+
+    pub fn is_revealed(&self, x: u8, y: u8) -> bool {
+        self.engine.tile(x, y).revealed
+    }
+
+    // None under fog
+    pub fn resource_at(&self, x: u8, y: u8) -> Option<ResourceType> {
+        self.is_revealed(x, y).then(|| self.engine.visible_resource_at(x, y))
+    }
+
+    pub fn kind_at(&self, x: u8, y: u8) -> Option<&ResourceKind> {
+        self.resource_at(x, y).filter(|r| !r.is_empty()).map(|r| self.catalog().kind(r))
+    }
+
+    pub fn is_fixed(&self, x: u8, y: u8) -> bool {
+        self.is_revealed(x, y) && self.engine.tile(x, y).fixed
+    }
+
+    // How far a revealed resource's waste has grown (1.0 under fog)
+    pub fn decay_at(&self, x: u8, y: u8) -> f32 {
+        if self.is_revealed(x, y) {
+            self.engine.tile(x, y).decay_multiplier
+        } else {
+            1.0
+        }
+    }
+
+    // Cheapest cost of sailing from the ship to (x, y), None when it can't get there
+    pub fn sailing_cost(&self, x: u8, y: u8) -> Option<i32> {
+        self.sailing_costs[x as usize][y as usize]
+    }
+
+    // Revealed resources the ship can reach that still need fixing
+    pub fn fix_targets(&self) -> Vec<(u8, u8)> {
+        self.tiles()
+            .filter(|&(x, y)| {
+                let tile = self.engine.tile(x, y);
+                self.kind_at(x, y).is_some()
+                    && !tile.fixed
+                    && tile.fixing_sprints_left == 0
+                    && self.sailing_cost(x, y).is_some()
+            })
+            .collect()
+    }
+
+    // Fogged tiles the ship can reach. Sailing onto one reveals it.
+    pub fn unexplored(&self) -> Vec<(u8, u8)> {
+        self.tiles()
+            .filter(|&(x, y)| !self.is_revealed(x, y) && self.sailing_cost(x, y).is_some())
+            .collect()
+    }
+
+    pub fn nearest_unexplored(&self) -> Option<(u8, u8)> {
+        self.unexplored().into_iter().min_by_key(|&(x, y)| self.sailing_cost(x, y))
+    }
+
+    // Revealed cardinal neighbors of (x, y) holding a resource, as (position, fixed)
+    pub fn known_neighbors(&self, x: u8, y: u8) -> impl Iterator<Item = ((u8, u8), bool)> + '_ {
+        [(0, 1), (0, -1), (1, 0), (-1, 0)]
+            .into_iter()
+            .map(move |(dx, dy)| (x as i32 + dx, y as i32 + dy))
+            .filter(|&(nx, ny)| nx >= 0 && ny >= 0 && nx < self.grid_width() as i32 && ny < self.grid_height() as i32)
+            .map(|(nx, ny)| (nx as u8, ny as u8))
+            .filter(|&(nx, ny)| self.kind_at(nx, ny).is_some())
+            .map(|(nx, ny)| ((nx, ny), self.is_fixed(nx, ny)))
+    }
+
+    // A copy of the game on `grid` for whatever the player can't see, with its own dice
+    pub fn imagine(&self, grid: &[Vec<ResourceType>], rng: GameRng) -> GameEngine {
+        self.engine.determinize(grid, rng)
+    }
+
+    fn tiles(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        (0..self.grid_width()).flat_map(move |x| (0..self.grid_height()).map(move |y| (x, y)))
+    }
+}
+
+pub trait Strategy {
+    // Next tile to sail to. A revealed resource there gets fixed on arrival; any other tile is
+    // a scouting trip. None means the bot has nothing left worth doing.
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)>;
+}
+
+// The built-in bots, after the play styles in the GDD
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum StrategyKind {
+    Greedy,
+    Rush,
+    Cluster,
+    Combo,
+    Balanced,
+    Random,
+    Search,
+}
+
+impl StrategyKind {
+    pub const ALL: [StrategyKind; 7] = [
+        Self::Greedy,
+        Self::Rush,
+        Self::Cluster,
+        Self::Combo,
+        Self::Balanced,
+        Self::Random,
+        Self::Search,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Greedy => "Greedy",
+            Self::Rush => "Rush",
+            Self::Cluster => "Cluster",
+            Self::Combo => "Combo",
+            Self::Balanced => "Balanced",
+            Self::Random => "Random",
+            Self::Search => "Search",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            Self::Greedy => "Best waste + savings per fix sprint in sight, wherever it is",
+            Self::Rush => "Hunts down critical resources first",
+            Self::Cluster => "Works through dense neighborhoods for adjacency bonuses",
+            Self::Combo => "Chains related resources to build the combo",
+            Self::Balanced => "Value per fix sprint, minus what it costs to sail there",
+            Self::Random => "Sails to a random resource or fogged tile",
+            Self::Search => "Plays each option out on maps imagined behind the fog",
+        }
+    }
+
+    // Case-insensitive lookup by name, for command-line tools
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.name().eq_ignore_ascii_case(name))
+    }
+
+    // A fresh bot for one game. Random and Search draw from their own RNG, derived from the
+    // game seed but on a different stream from the engine's, so they never disturb its rolls
+    // or echo the draws that built the map.
+    pub fn build(&self, seed: u64) -> Box<dyn Strategy> {
+        match self {
+            Self::Greedy => Box::new(Greedy),
+            Self::Rush => Box::new(Rush),
+            Self::Cluster => Box::new(Cluster),
+            Self::Combo => Box::new(Combo),
+            Self::Balanced => Box::new(Balanced),
+            Self::Random => Box::new(RandomPlay { rng: bot_rng(seed) }),
+            Self::Search => Box::new(Search { rng: bot_rng(seed) }),
+        }
+    }
+}
+
+fn bot_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed ^ BOT_STREAM)
+}
+
+// Plays until the game ends or the bot runs out of ideas
+pub fn play(engine: &mut GameEngine, strategy: &mut dyn Strategy) {
+    while !engine.is_over() {
+        let Some((x, y)) = strategy.next_target(&PlayerView::new(engine)) else {
+            break;
+        };
+        if !sail_and_fix(engine, x, y) {
+            break;
+        }
+    }
+}
+
+// Sails to (x, y) and fixes whatever is revealed there. False once the bot can't get anywhere,
+// or when the fix still hasn't stuck after `MAX_FIX_ATTEMPTS` tries.
+pub fn sail_and_fix(engine: &mut GameEngine, x: u8, y: u8) -> bool {
+    let Some(path) = engine.find_path(x, y) else {
+        return false;
+    };
+    if path.is_empty() && !needs_fix(engine, x, y) {
+        // Already here with nothing to do, so the bot is stuck
+        return false;
+    }
+
+    for heading in path {
+        if engine.is_over() {
+            break;
+        }
+        engine.apply(Action::Move(heading));
+    }
+    if engine.is_over() || (engine.data().player_x, engine.data().player_y) != (x, y) {
+        return false;
+    }
+    if !needs_fix(engine, x, y) {
+        return true;
+    }
+
+    // Critical fixes can fail, so keep retrying until it sticks
+    let mut attempts = 0;
+    while !engine.is_over() && !engine.tile(x, y).fixed {
+        if attempts == MAX_FIX_ATTEMPTS {
+            return false;
+        }
+        attempts += 1;
+        engine.apply(Action::Fix);
+        while !engine.is_over() && engine.tile(x, y).fixing_sprints_left > 0 {
+            engine.apply(Action::AdvanceSprint);
+        }
+    }
+    true
+}
+
+fn needs_fix(engine: &GameEngine, x: u8, y: u8) -> bool {
+    let tile = engine.tile(x, y);
+    tile.revealed && !tile.fixed && !engine.visible_resource_at(x, y).is_empty()
+}
+
+// Monthly waste plus midpoint savings, per sprint of work
+fn efficiency(kind: &ResourceKind) -> f32 {
+    let (min_savings, max_savings) = kind.savings_range;
+    (kind.waste_cost + (min_savings + max_savings) / 2) as f32 / kind.fix_sprints as f32
+}
+
+// Efficiency with decay and the odds of the fix failing, minus the budget spent sailing there
+fn net_value(view: &PlayerView, x: u8, y: u8) -> f32 {
+    let Some(kind) = view.kind_at(x, y) else {
+        return f32::MIN;
+    };
+    let (min_savings, max_savings) = kind.savings_range;
+    let value = kind.waste_cost as f32 * view.decay_at(x, y) + (min_savings + max_savings) as f32 / 2.0;
+    let per_sprint = value * (1.0 - kind.fix_failure_chance) / kind.fix_sprints as f32;
+    per_sprint - view.sailing_cost(x, y).unwrap_or(0) as f32 * TRAVEL_WEIGHT
+}
+
+fn best_by(targets: Vec<(u8, u8)>, mut score: impl FnMut((u8, u8)) -> f32) -> Option<(u8, u8)> {
+    targets
+        .into_iter()
+        .map(|target| (score(target), target))
+        .fold(None, |best: Option<(f32, (u8, u8))>, (s, target)| match best {
+            Some((b, _)) if b >= s => best,
+            _ => Some((s, target)),
+        })
+        .map(|(_, target)| target)
+}
+
+// The original simulator bot: best efficiency in sight, however far away, and scouting only
+// once nothing is in sight
+struct Greedy;
+
+impl Strategy for Greedy {
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)> {
+        best_by(view.fix_targets(), |(x, y)| view.kind_at(x, y).map_or(f32::MIN, efficiency))
+            .or_else(|| view.nearest_unexplored())
+    }
+}
+
+// Criticals first, scouting for more while any fog is left, then mops up by waste
+struct Rush;
+
+impl Strategy for Rush {
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)> {
+        let targets = view.fix_targets();
+        let criticals: Vec<_> = targets
+            .iter()
+            .copied()
+            .filter(|&(x, y)| view.kind_at(x, y).is_some_and(|kind| kind.is_critical()))
+            .collect();
+        let waste = |(x, y): (u8, u8)| view.kind_at(x, y).map_or(f32::MIN, |kind| kind.waste_cost as f32 * view.decay_at(x, y));
+
+        best_by(criticals, waste)
+            .or_else(|| view.nearest_unexplored())
+            .or_else(|| best_by(targets, waste))
+    }
+}
+
+// Prefers resources with known neighbors, since fixed neighbors pay an adjacency bonus
+struct Cluster;
+
+impl Strategy for Cluster {
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)> {
+        best_by(view.fix_targets(), |(x, y)| {
+            let neighbors: f32 = view.known_neighbors(x, y).map(|(_, fixed)| if fixed { 2.0 } else { 1.0 }).sum();
+            let value = net_value(view, x, y);
+            if value > 0.0 {
+                value * (1.0 + CLUSTER_WEIGHT * neighbors)
+            } else {
+                value
+            }
+        })
+        .or_else(|| view.nearest_unexplored())
+    }
+}
+
+// Chains fixes off the last one to keep the combo climbing
+struct Combo;
+
+impl Strategy for Combo {
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)> {
+        let last = view.data().resources_fixed_in_order.last().copied();
+        best_by(view.fix_targets(), |(x, y)| {
+            let value = net_value(view, x, y);
+            let chains = match (last, view.resource_at(x, y)) {
+                (Some(last), Some(resource)) => view.catalog().are_related(last, resource),
+                _ => false,
+            };
+            if chains && value > 0.0 {
+                value * CHAIN_WEIGHT
+            } else {
+                value
+            }
+        })
+        .or_else(|| view.nearest_unexplored())
+    }
+}
+
+// Weighs value against distance, and scouts when nothing in sight is worth the trip
+struct Balanced;
+
+impl Strategy for Balanced {
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)> {
+        let targets = view.fix_targets();
+        let best = best_by(targets, |(x, y)| net_value(view, x, y));
+        match best {
+            Some((x, y)) if net_value(view, x, y) > 0.0 => Some((x, y)),
+            _ => view.nearest_unexplored().or(best),
+        }
+    }
+}
+
+// A sailor with no plan: any reachable resource or fogged tile, picked at random
+struct RandomPlay {
+    rng: StdRng,
+}
+
+impl Strategy for RandomPlay {
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)> {
+        let mut candidates = view.fix_targets();
+        candidates.extend(view.unexplored());
+        candidates.choose(&mut self.rng).copied()
+    }
+}
+
+// Determinized Monte Carlo search, the closest thing here to a strong human. At each decision
+// it imagines maps that fit everything it has seen, plays each candidate move out on every map
+// with the Greedy bot finishing the game, and picks the move that did best across them. Every
+// candidate gets the same maps and dice, so luck cancels out between them.
+struct Search {
+    rng: StdRng,
+}
+
+impl Strategy for Search {
+    fn next_target(&mut self, view: &PlayerView) -> Option<(u8, u8)> {
+        let mut targets = view.fix_targets();
+        targets.sort_by(|&(ax, ay), &(bx, by)| net_value(view, bx, by).total_cmp(&net_value(view, ax, ay)));
+        targets.truncate(SEARCH_CANDIDATES);
+        let mut candidates = Vec::new();
+        for target in Greedy.next_target(view).into_iter().chain(targets).chain(view.nearest_unexplored()) {
+            if !candidates.contains(&target) {
+                candidates.push(target);
+            }
+        }
+        if candidates.len() <= 1 {
+            return candidates.first().copied();
+        }
+
+        let mut scores = vec![0.0; candidates.len()];
+        for _ in 0..SEARCH_SAMPLES {
+            let grid = sample_hidden_grid(view, &mut self.rng);
+            let dice: u64 = self.rng.gen();
+            for (score, &(x, y)) in scores.iter_mut().zip(&candidates) {
+                let mut engine = view.imagine(&grid, GameRng::new(dice));
+                if sail_and_fix(&mut engine, x, y) {
+                    play(&mut engine, &mut Greedy);
+                }
+                *score += playout_score(engine.data());
+            }
+        }
+        // Ties go to the earlier candidate, so Greedy's pick wins when nothing beats it
+        let best = (0..candidates.len()).fold(0, |best, i| if scores[i] > scores[best] { i } else { best });
+        Some(candidates[best])
+    }
+}
+
+// A map that fits what the player has seen: known tiles as they look, and the rest of the
+// preset's resources dealt onto fogged open water by spawn weight. Terrain only ever lands on
+// empty tiles, so fogged reefs, storms and the like are known to be clear. Clear water that has
+// been seen is taken at face value, even though hidden waste could be under it.
+fn sample_hidden_grid(view: &PlayerView, rng: &mut StdRng) -> Vec<Vec<ResourceType>> {
+    let mut grid = vec![vec![ResourceType::EMPTY; view.grid_height() as usize]; view.grid_width() as usize];
+    let mut known = 0;
+    let mut fogged = Vec::new();
+    for (x, y) in view.tiles() {
+        match view.resource_at(x, y) {
+            Some(resource) => {
+                grid[x as usize][y as usize] = resource;
+                known += !resource.is_empty() as usize;
+            }
+            None if view.terrain_at(x, y) == Terrain::OpenWater => fogged.push((x, y)),
+            None => {}
+        }
+    }
+
+    let settings = &view.data().settings;
+    let remaining = resource_count(view.grid_width(), view.grid_height(), settings.resource_density).saturating_sub(known);
+    fogged.shuffle(rng);
+    for &(x, y) in fogged.iter().take(remaining) {
+        grid[x as usize][y as usize] = view.catalog().random_resource(rng);
+    }
+    grid
+}
+
+// Wins first, then the budget left. A loss is better the closer it got: more savings coming in
+// and less waste left running.
+fn playout_score(data: &GameData) -> f32 {
+    if data.game_won {
+        SEARCH_WIN_SCORE + data.budget as f32
+    } else {
+        data.monthly_savings as f32 - data.total_waste as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::resources::Difficulty;
+
+    fn engine(seed: u64) -> GameEngine {
+        GameEngine::new(GameData::new(Difficulty::Normal), GameRng::new(seed))
+    }
+
+    // A few of Greedy's fixes in, so there is a revealed patch, decay and fog to work with
+    fn midgame(seed: u64) -> GameEngine {
+        let mut engine = engine(seed);
+        for _ in 0..3 {
+            let Some((x, y)) = Greedy.next_target(&PlayerView::new(&engine)) else {
+                break;
+            };
+            if engine.is_over() || !sail_and_fix(&mut engine, x, y) {
+                break;
+            }
+        }
+        engine
+    }
+
+    #[test]
+    fn search_plays_a_seed_the_same_way_twice() {
+        let play_out = |seed: u64| {
+            let mut engine = engine(seed);
+            play(&mut engine, StrategyKind::Search.build(seed).as_mut());
+            engine.data().clone()
+        };
+        for seed in [3, 8] {
+            assert_eq!(play_out(seed), play_out(seed), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn search_never_looks_under_the_fog() {
+        for seed in 0..6 {
+            let engine = midgame(seed);
+            if engine.is_over() {
+                continue;
+            }
+            // Same view, different map under the fog: every unseen tile holds something else
+            let mut rng = StdRng::seed_from_u64(seed);
+            let grid: Vec<Vec<ResourceType>> = (0..engine.grid_width())
+                .map(|_| (0..engine.grid_height()).map(|_| engine.catalog().random_resource(&mut rng)).collect())
+                .collect();
+            let disguised = engine.determinize(&grid, GameRng::new(seed + 100));
+
+            let real = StrategyKind::Search.build(seed).next_target(&PlayerView::new(&engine));
+            let fake = StrategyKind::Search.build(seed).next_target(&PlayerView::new(&disguised));
+            assert_eq!(real, fake, "seed {}", seed);
+        }
+    }
+
+    // 7x7 open sea with the ship at (3, 3) facing south, so (3, 3), (2, 2), (3, 2) and (4, 2)
+    // start revealed. Nothing starts hidden unless `hidden_chance` says so.
+    fn chart(resources: &[(&str, u8, u8)], move_cost: i32, hidden_chance: f32) -> GameEngine {
+        let catalog = ResourceCatalog::builtin_shared();
+        let mut config = GameConfig::builtin();
+        config.hidden_chance = hidden_chance;
+        config.difficulties.normal.move_cost = move_cost;
+        let data = GameData::with_config(Difficulty::Normal, 7, 7, Arc::new(config));
+        let mut grid = vec![vec![ResourceType::EMPTY; 7]; 7];
+        for &(id, x, y) in resources {
+            grid[x as usize][y as usize] = catalog.find(id).unwrap();
+        }
+        let mut terrain = vec![vec![Terrain::OpenWater; 7]; 7];
+        terrain[6][6] = Terrain::Reef;
+        GameEngine::with_grid(data, grid, terrain, GameRng::new(1), catalog)
+    }
+
+    fn pick(strategy: &mut dyn Strategy, engine: &GameEngine) -> Option<(u8, u8)> {
+        strategy.next_target(&PlayerView::new(engine))
+    }
+
+    #[test]
+    fn view_hides_fog_and_hidden_waste() {
+        // The disk is related to the VM and cheaper, so it starts hidden
+        let engine = chart(&[("IdleVM", 3, 2), ("OrphanedDisk", 4, 2), ("IdleGPUCluster", 0, 6)], 50, 1.0);
+        assert!(engine.tile(4, 2).hidden);
+        let view = PlayerView::new(&engine);
+
+        assert_eq!(view.kind_at(3, 2).map(|kind| kind.id.as_str()), Some("IdleVM"));
+        assert_eq!(view.resource_at(4, 2), Some(ResourceType::EMPTY));
+        assert!(view.kind_at(4, 2).is_none());
+        assert_eq!(view.resource_at(0, 6), None);
+        assert!(view.kind_at(0, 6).is_none());
+        assert_eq!(view.fix_targets(), vec![(3, 2)]);
+
+        let unexplored = view.unexplored();
+        assert!(unexplored.contains(&(0, 6)));
+        assert!(!unexplored.contains(&(3, 2)) && !unexplored.contains(&(4, 2)));
+        assert!(!unexplored.contains(&(6, 6)), "reefs can't be sailed to");
+        assert_eq!(view.nearest_unexplored().and_then(|(x, y)| view.sailing_cost(x, y)), Some(50));
+    }
+
+    #[test]
+    fn greedy_rush_and_balanced_pick_by_their_own_measure() {
+        // Missed reservations pay best per sprint, OpenShift wastes most, and with sailing this
+        // dear the database underfoot is the best value once the trip is counted
+        let engine = chart(
+            &[("UnusedSQLDatabase", 3, 3), ("OverprovisionedOpenShift", 3, 2), ("MissedReservation", 2, 2)],
+            150,
+            0.0,
+        );
+        assert_eq!(pick(&mut Greedy, &engine), Some((2, 2)));
+        assert_eq!(pick(&mut Rush, &engine), Some((3, 2)));
+        assert_eq!(pick(&mut Balanced, &engine), Some((3, 3)));
+    }
+
+    #[test]
+    fn cluster_prefers_the_resource_with_neighbors() {
+        let engine = chart(
+            &[("IdleVM", 3, 2), ("UnusedSQLDatabase", 2, 2), ("OrphanedDisk", 4, 2), ("StaleSnapshot", 3, 3)],
+            50,
+            0.0,
+        );
+        // On value alone the database wins, but the VM sits among three known resources
+        assert_eq!(pick(&mut Balanced, &engine), Some((2, 2)));
+        assert_eq!(pick(&mut Cluster, &engine), Some((3, 2)));
+    }
+
+    #[test]
+    fn combo_chains_off_the_last_fix() {
+        let mut engine = chart(&[("OrphanedDisk", 3, 3), ("StaleSnapshot", 3, 2), ("LogIngestionBloat", 2, 2)], 50, 0.0);
+        assert!(sail_and_fix(&mut engine, 3, 3));
+        // The log bloat is worth more, but the snapshot is related to the disk just fixed
+        assert_eq!(pick(&mut Balanced, &engine), Some((2, 2)));
+        assert_eq!(pick(&mut Combo, &engine), Some((3, 2)));
+    }
+
+    #[test]
+    fn greedy_and_balanced_scout_when_nothing_is_in_sight() {
+        let engine = chart(&[("IdleVM", 0, 6)], 50, 0.0);
+        let view = PlayerView::new(&engine);
+        assert!(view.fix_targets().is_empty());
+        let nearest = view.nearest_unexplored();
+        assert!(nearest.is_some());
+        assert_eq!(pick(&mut Greedy, &engine), nearest);
+        assert_eq!(pick(&mut Balanced, &engine), nearest);
+    }
+
+    #[test]
+    fn random_picks_a_target_or_fogged_tile() {
+        let engine = chart(&[("IdleVM", 3, 2), ("OrphanedDisk", 0, 6)], 50, 0.0);
+        let view = PlayerView::new(&engine);
+        let mut options = view.fix_targets();
+        options.extend(view.unexplored());
+        for seed in 0..8 {
+            let first = pick(StrategyKind::Random.build(seed).as_mut(), &engine);
+            assert!(first.is_some_and(|target| options.contains(&target)));
+            assert_eq!(pick(StrategyKind::Random.build(seed).as_mut(), &engine), first);
+        }
+    }
+
+    #[test]
+    fn sail_and_fix_reports_a_stuck_bot() {
+        let mut engine = chart(&[("IdleVM", 3, 2)], 50, 0.0);
+        // Unreachable, and already here with nothing to fix
+        assert!(!sail_and_fix(&mut engine, 6, 6));
+        assert!(!sail_and_fix(&mut engine, 3, 3));
+        assert_eq!(engine.data().sprint, 0);
+
+        assert!(sail_and_fix(&mut engine, 3, 2));
+        assert!(engine.tile(3, 2).fixed);
+        // Sailing onto a fogged tile is a scouting trip, not a failure
+        assert!(sail_and_fix(&mut engine, 0, 0));
+        assert!(engine.tile(0, 0).revealed);
+    }
+
+    // One kind of waste that never fixes, in a game that never burns
+    const DOOMED_CATALOG: &str = r#"(
+        resources: [
+            (
+                id: "Doomed",
+                name: "Doomed",
+                category: Critical,
+                waste_cost: 100,
+                fix_sprints: 1,
+                savings_range: (100, 100),
+                hint: "",
+                icon: "",
+                color: (1.0, 1.0, 1.0),
+                sprite: "",
+                spawn_weight: 1,
+                fix_failure_chance: 1.0,
+            ),
+        ],
+        relations: [],
+    )"#;
+
+    #[test]
+    fn bots_draw_from_a_different_stream_than_the_map() {
+        for seed in [0, 1, 42] {
+            let mut map = GameRng::new(seed);
+            let mut bot = bot_rng(seed);
+            let map_draws: Vec<u64> = (0..4).map(|_| map.gen()).collect();
+            let bot_draws: Vec<u64> = (0..4).map(|_| bot.gen()).collect();
+            assert_ne!(map_draws, bot_draws);
+        }
+    }
+
+    #[test]
+    fn sail_and_fix_gives_up_on_a_fix_that_never_sticks() {
+        let catalog = Arc::new(ResourceCatalog::from_ron(DOOMED_CATALOG).unwrap());
+        let mut config = GameConfig::builtin();
+        config.base_burn = 0;
+        config.difficulties.normal.burn_coefficient = 0.0;
+        config.difficulties.normal.move_cost = 0;
+        let data = GameData::with_config(Difficulty::Normal, 5, 5, Arc::new(config));
+        let mut grid = vec![vec![ResourceType::EMPTY; 5]; 5];
+        grid[2][2] = catalog.find("Doomed").unwrap();
+        let terrain = vec![vec![Terrain::OpenWater; 5]; 5];
+        let mut engine = GameEngine::with_grid(data, grid, terrain, GameRng::new(1), catalog);
+
+        assert!(!sail_and_fix(&mut engine, 2, 2));
+        assert!(!engine.is_over());
+        assert_eq!(engine.data().failed_fix_attempts, MAX_FIX_ATTEMPTS);
+    }
+}
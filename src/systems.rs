@@ -1,137 +1,116 @@
+use std::sync::Arc;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::catalog::*;
 use crate::components::*;
+use crate::config::*;
+use crate::engine::*;
 use crate::resources::*;
 
-// Helper function to check if resources are related (for combo system)
-fn are_resources_related(a: &ResourceType, b: &ResourceType) -> bool {
-    use ResourceType::*;
-    match (a, b) {
-        // Compute resources
-        (IdleVM, IdleGPUCluster) | (IdleGPUCluster, IdleVM) => true,
-        (IdleVM, MissedReservation) | (MissedReservation, IdleVM) => true,
-
-        // Storage resources
-        (OrphanedDisk, StaleSnapshot) | (StaleSnapshot, OrphanedDisk) => true,
-        (OrphanedDisk, ExpiredBackups) | (ExpiredBackups, OrphanedDisk) => true,
-        (EmptyStorageAccount, OrphanedDisk) | (OrphanedDisk, EmptyStorageAccount) => true,
-
-        // Database resources
-        (UnusedSQLDatabase, OverprovisionedCosmosDB) | (OverprovisionedCosmosDB, UnusedSQLDatabase) => true,
-
-        // Container/OpenShift resources
-        (OverprovisionedOpenShift, OversizedAppService) | (OversizedAppService, OverprovisionedOpenShift) => true,
-        (OverprovisionedOpenShift, RedundantLoadBalancer) | (RedundantLoadBalancer, OverprovisionedOpenShift) => true,
-
-        // Networking resources
-        (UnusedPublicIP, RedundantLoadBalancer) | (RedundantLoadBalancer, UnusedPublicIP) => true,
-        (UnusedPublicIP, IdleCDNEndpoint) | (IdleCDNEndpoint, UnusedPublicIP) => true,
-
-        // Logging
-        (LogIngestionBloat, ExpiredBackups) | (ExpiredBackups, LogIngestionBloat) => true,
-
-        _ => false,
-    }
-}
-
-// Count adjacent fixed tiles
-fn count_adjacent_fixed_from_positions(
-    fixed_positions: &[(u8, u8)],
-    x: u8,
-    y: u8,
-    grid_size: u8,
-) -> usize {
-    let mut count = 0;
-    let adjacent = [
-        (x.wrapping_sub(1), y),
-        (x + 1, y),
-        (x, y.wrapping_sub(1)),
-        (x, y + 1),
-    ];
-
-    for &(px, py) in &adjacent {
-        if px < grid_size && py < grid_size {
-            if fixed_positions.contains(&(px, py)) {
-                count += 1;
-            }
-        }
-    }
-    count
-}
-
+#[allow(clippy::too_many_arguments)]
 pub fn setup_game(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut game_data: ResMut<GameData>,
+    rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+    map_size: Res<MapSize>,
+    catalogs: Res<Assets<ResourceCatalog>>,
+    catalog_handle: Res<ResourceCatalogHandle>,
+    configs: Res<Assets<GameConfig>>,
+    config_handle: Res<GameConfigHandle>,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
 ) {
-    // Reset game data for a fresh start - always use Normal difficulty
-    *game_data = GameData::new(Difficulty::Normal);
-
-    // Create ocean background
-    commands.spawn(SpriteBundle {
-        sprite: Sprite {
-            color: Color::srgb(0.05, 0.15, 0.25), // Deep ocean blue
-            custom_size: Some(Vec2::new(1200.0, 900.0)),
+    // Use the loaded catalog asset, falling back to the embedded one if it failed to load
+    let catalog = match catalogs.get(&catalog_handle.0) {
+        Some(catalog) => catalog.clone(),
+        None => {
+            warn!("Resource catalog not loaded, using the built-in catalog");
+            ResourceCatalog::builtin()
+        }
+    };
+
+    // Fresh engine for every game, using the difficulty and map size picked on the menu
+    let config = configs.get(&config_handle.0).cloned().unwrap_or_else(|| {
+        warn!("Game config not loaded, using the built-in config");
+        GameConfig::builtin()
+    });
+    let (width, height) = map_size.dimensions(&config.settings(*difficulty));
+    let game_data = GameData::with_config(*difficulty, width, height, Arc::new(config));
+    let engine = GameEngine::with_catalog(game_data, rng.clone(), Arc::new(catalog));
+    let game_data = engine.data();
+    let layout = TileLayout::centered(game_data.grid_width, game_data.grid_height, TileLayout::TILE_SIZE);
+    let tile_size = layout.tile_size;
+    let map_size = layout.map_size();
+
+    // Create ocean background, with room around the map for panning
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.05, 0.15, 0.25), // Deep ocean blue
+                custom_size: Some(map_size + Vec2::new(1200.0, 900.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, -10.0),
             ..default()
         },
-        transform: Transform::from_xyz(0.0, 0.0, -10.0),
-        ..default()
-    });
+        MapBackground,
+    ));
 
     // Add grid overlay for better visibility
-    commands.spawn(SpriteBundle {
-        sprite: Sprite {
-            color: Color::srgba(0.2, 0.3, 0.4, 0.3), // Semi-transparent grid background
-            custom_size: Some(Vec2::new(
-                game_data.grid_size as f32 * 64.0 + 20.0,
-                game_data.grid_size as f32 * 64.0 + 20.0
-            )),
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.2, 0.3, 0.4, 0.3), // Semi-transparent grid background
+                custom_size: Some(map_size + Vec2::splat(20.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, -2.0),
             ..default()
         },
-        transform: Transform::from_xyz(0.0, 0.0, -2.0),
-        ..default()
-    });
-
-    let grid = generate_random_grid(game_data.grid_size);
-    let tile_size = 64.0;
-    let grid_offset_x = -(game_data.grid_size as f32 * tile_size) / 2.0 + tile_size / 2.0;
-    let grid_offset_y = -(game_data.grid_size as f32 * tile_size) / 2.0 + tile_size / 2.0;
+        MapBackground,
+    ));
 
     // Create paths between tiles
-    for x in 0..game_data.grid_size {
-        for y in 0..game_data.grid_size {
-            let world_x = grid_offset_x + (x as f32 * tile_size);
-            let world_y = grid_offset_y + (y as f32 * tile_size);
+    for x in 0..game_data.grid_width {
+        for y in 0..game_data.grid_height {
+            let world = layout.world_position(x, y);
 
             // Grid lines for better visibility
-            if x < game_data.grid_size - 1 {
-                commands.spawn(SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::srgba(0.4, 0.5, 0.6, 0.3), // Semi-transparent grid line
-                        custom_size: Some(Vec2::new(tile_size, 2.0)),
+            if x < game_data.grid_width - 1 {
+                commands.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::srgba(0.4, 0.5, 0.6, 0.3), // Semi-transparent grid line
+                            custom_size: Some(Vec2::new(tile_size, 2.0)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(world.x + tile_size/2.0, world.y, -1.0),
                         ..default()
                     },
-                    transform: Transform::from_xyz(world_x + tile_size/2.0, world_y, -1.0),
-                    ..default()
-                });
+                    MapBackground,
+                ));
             }
 
-            if y < game_data.grid_size - 1 {
-                commands.spawn(SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::srgba(0.4, 0.5, 0.6, 0.3), // Semi-transparent grid line
-                        custom_size: Some(Vec2::new(2.0, tile_size)),
+            if y < game_data.grid_height - 1 {
+                commands.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::srgba(0.4, 0.5, 0.6, 0.3), // Semi-transparent grid line
+                            custom_size: Some(Vec2::new(2.0, tile_size)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(world.x, world.y + tile_size/2.0, -1.0),
                         ..default()
                     },
-                    transform: Transform::from_xyz(world_x, world_y + tile_size/2.0, -1.0),
-                    ..default()
-                });
+                    MapBackground,
+                ));
             }
         }
     }
 
-    // Spawn player at center
-    let player_world_x = grid_offset_x + (game_data.player_x as f32 * tile_size);
-    let player_world_y = grid_offset_y + (game_data.player_y as f32 * tile_size);
+    // Spawn player at the center of the map
+    let player_world = layout.world_position(game_data.player_x, game_data.player_y);
 
     // Load player ship sprite
     commands.spawn((
@@ -141,7 +120,8 @@ pub fn setup_game(
                 custom_size: Some(Vec2::new(tile_size * 0.8, tile_size * 0.8)),
                 ..default()
             },
-            transform: Transform::from_xyz(player_world_x, player_world_y, 10.0),
+            transform: Transform::from_xyz(player_world.x, player_world.y, 10.0)
+                .with_rotation(heading_rotation(engine.heading())),
             ..default()
         },
         Player {
@@ -151,14 +131,11 @@ pub fn setup_game(
         PlayerMarker,
     ));
 
-    for x in 0..game_data.grid_size {
-        for y in 0..game_data.grid_size {
-            let world_x = grid_offset_x + (x as f32 * tile_size);
-            let world_y = grid_offset_y + (y as f32 * tile_size);
-
-            let resource_type = grid[x as usize][y as usize];
+    for x in 0..game_data.grid_width {
+        for y in 0..game_data.grid_height {
+            let world = layout.world_position(x, y);
 
-            // Base tile - foggy/unknown area
+            // Base tile - foggy/unknown area. TileState mirrors the engine for visuals.
             commands.spawn((
                 SpriteBundle {
                     sprite: Sprite {
@@ -166,406 +143,719 @@ pub fn setup_game(
                         custom_size: Some(Vec2::new(tile_size - 2.0, tile_size - 2.0)),
                         ..default()
                     },
-                    transform: Transform::from_xyz(world_x, world_y, 0.0),
+                    transform: Transform::from_xyz(world.x, world.y, 0.0),
                     ..default()
                 },
                 Tile { x, y },
                 TileState::default(),
-                resource_type,
+                engine.resource_at(x, y),
+                engine.terrain_at(x, y),
             ));
 
-            // Calculate initial total waste from ALL resources on the map
-            if !matches!(resource_type, ResourceType::Empty) {
-                game_data.total_waste += resource_type.waste_cost();
-            }
+            spawn_terrain(&mut commands, &asset_server, engine.terrain_at(x, y), world, tile_size);
         }
     }
 
-    // Total waste now includes ALL waste on map, not just revealed
+    // Start the camera on the ship instead of sweeping over from the map center
+    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+        camera_transform.translation.x = player_world.x;
+        camera_transform.translation.y = player_world.y;
+    }
+
+    commands.insert_resource(layout);
+    commands.insert_resource(engine);
 }
 
-pub fn handle_player_movement(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<(&mut Transform, &mut Player), With<PlayerMarker>>,
-    mut game_data: ResMut<GameData>,
-) {
-    let Ok((mut transform, mut player)) = player_query.get_single_mut() else {
+// Terrain is charted, so it's drawn above the fog of war
+fn spawn_terrain(commands: &mut Commands, asset_server: &AssetServer, terrain: Terrain, world: Vec2, tile_size: f32) {
+    let sprite_path = terrain.get_sprite_path();
+    if sprite_path.is_empty() {
         return;
-    };
+    }
 
-    let mut moved = false;
-    let mut new_x = player.x;
-    let mut new_y = player.y;
-    let mut target_rotation = transform.rotation; // Keep current rotation by default
+    let (color, size) = match terrain {
+        Terrain::Reef => (Color::WHITE, tile_size * 0.9),
+        Terrain::Island => (Color::WHITE, tile_size * 0.7),
+        Terrain::Storm => (Color::srgba(0.35, 0.35, 0.45, 0.9), tile_size),  // Dark storm clouds
+        _ => (Color::srgba(1.0, 1.0, 1.0, 0.6), tile_size),
+    };
 
-    if keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW) {
-        if player.y < game_data.grid_size - 1 {
-            new_y += 1;
-            moved = true;
-            target_rotation = Quat::from_rotation_z(std::f32::consts::PI); // Face down visually when going up
-        }
-    }
-    if keyboard.just_pressed(KeyCode::ArrowDown) || keyboard.just_pressed(KeyCode::KeyS) {
-        if player.y > 0 {
-            new_y -= 1;
-            moved = true;
-            target_rotation = Quat::from_rotation_z(0.0); // Face up visually when going down
-        }
-    }
-    if keyboard.just_pressed(KeyCode::ArrowLeft) || keyboard.just_pressed(KeyCode::KeyA) {
-        if player.x > 0 {
-            new_x -= 1;
-            moved = true;
-            target_rotation = Quat::from_rotation_z(-std::f32::consts::PI / 2.0); // Face right visually when going left
-        }
+    // Islands get a sandy base under the palm
+    if terrain == Terrain::Island {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgb(0.85, 0.75, 0.5),
+                    custom_size: Some(Vec2::splat(tile_size - 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(world.x, world.y, 1.0),
+                ..default()
+            },
+            MapBackground,
+        ));
     }
-    if keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::KeyD) {
-        if player.x < game_data.grid_size - 1 {
-            new_x += 1;
-            moved = true;
-            target_rotation = Quat::from_rotation_z(std::f32::consts::PI / 2.0); // Face left visually when going right
-        }
+
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load(sprite_path.to_string()),
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(size)),
+                ..default()
+            },
+            transform: Transform::from_xyz(world.x, world.y, 2.0),
+            ..default()
+        },
+        MapBackground,
+    ));
+
+    // Currents show which way they push
+    if let Terrain::Current(drift) = terrain {
+        let angle = match drift {
+            Heading::East => 0.0,
+            Heading::North => std::f32::consts::PI / 2.0,
+            Heading::West => std::f32::consts::PI,
+            Heading::South => -std::f32::consts::PI / 2.0,
+        };
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    "»",
+                    TextStyle {
+                        font_size: 36.0,
+                        color: Color::srgb(0.9, 0.95, 1.0),
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(world.x, world.y, 3.0).with_rotation(Quat::from_rotation_z(angle)),
+                ..default()
+            },
+            MapBackground,
+        ));
     }
+}
 
-    if moved {
-        // Update player position
-        player.x = new_x;
-        player.y = new_y;
-        game_data.player_x = new_x;
-        game_data.player_y = new_y;
+// Ship sprite rotation for a heading (the sprite faces opposite to its travel heading)
+fn heading_rotation(heading: Heading) -> Quat {
+    match heading {
+        Heading::North => Quat::from_rotation_z(std::f32::consts::PI),
+        Heading::South => Quat::from_rotation_z(0.0),
+        Heading::West => Quat::from_rotation_z(-std::f32::consts::PI / 2.0),
+        Heading::East => Quat::from_rotation_z(std::f32::consts::PI / 2.0),
+    }
+}
 
-        // Update visual position
-        let tile_size = 64.0;
-        let grid_offset_x = -(game_data.grid_size as f32 * tile_size) / 2.0 + tile_size / 2.0;
-        let grid_offset_y = -(game_data.grid_size as f32 * tile_size) / 2.0 + tile_size / 2.0;
+pub fn load_resource_catalog(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ResourceCatalogHandle(asset_server.load(CATALOG_PATH)));
+}
 
-        transform.translation.x = grid_offset_x + (new_x as f32 * tile_size);
-        transform.translation.y = grid_offset_y + (new_y as f32 * tile_size);
+pub fn load_game_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameConfigHandle(asset_server.load(CONFIG_PATH)));
+}
 
-        // Apply rotation directly
-        transform.rotation = target_rotation;
+// Applies edits to the config file while a game is running (file watching is on in debug builds)
+pub fn hot_reload_game_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    configs: Res<Assets<GameConfig>>,
+    config_handle: Res<GameConfigHandle>,
+    engine: Option<ResMut<GameEngine>>,
+) {
+    let Some(mut engine) = engine else {
+        return;
+    };
 
-        // Fixed movement cost for Normal difficulty
-        game_data.budget -= 50;
-        if game_data.budget <= 0 {
-            game_data.game_lost = true;
+    for event in events.read() {
+        if !event.is_modified(&config_handle.0) {
+            continue;
+        }
+        if let Some(config) = configs.get(&config_handle.0) {
+            engine.replace_config(Arc::new(config.clone()));
+            info!("🔄 Game config reloaded");
         }
     }
 }
 
-// Auto-reveal system that scans ahead automatically
-pub fn auto_reveal_current_tile(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut tiles: Query<(Entity, &mut Sprite, &mut TileState, &ResourceType, &Tile)>,
-    mut game_data: ResMut<GameData>,
-    player_query: Query<&Transform, With<PlayerMarker>>,
+// Applies edits to the catalog file while a game is running (file watching is on in debug builds)
+pub fn hot_reload_resource_catalog(
+    mut events: EventReader<AssetEvent<ResourceCatalog>>,
+    catalogs: Res<Assets<ResourceCatalog>>,
+    catalog_handle: Res<ResourceCatalogHandle>,
+    engine: Option<ResMut<GameEngine>>,
 ) {
-    // Get player's movement direction based on ship rotation
-    let direction = if let Ok(transform) = player_query.get_single() {
-        let angle = transform.rotation.to_euler(bevy::math::EulerRot::ZYX).0;
-
-        if (angle - std::f32::consts::PI).abs() < 0.1 || (angle + std::f32::consts::PI).abs() < 0.1 {
-            // Rotation PI or -PI = ship moved UP (north) in grid
-            (0, 1)  // Scan north (ahead in movement direction)
-        } else if angle.abs() < 0.1 {
-            // Rotation 0 = ship moved DOWN (south) in grid
-            (0, -1)  // Scan south (ahead in movement direction)
-        } else if (angle - std::f32::consts::PI / 2.0).abs() < 0.1 {
-            // Rotation PI/2 = ship moved RIGHT (east) in grid
-            (1, 0)  // Scan east (ahead in movement direction)
-        } else if (angle + std::f32::consts::PI / 2.0).abs() < 0.1 {
-            // Rotation -PI/2 = ship moved LEFT (west) in grid
-            (-1, 0)  // Scan west (ahead in movement direction)
+    let Some(mut engine) = engine else {
+        return;
+    };
+
+    for event in events.read() {
+        if !event.is_modified(&catalog_handle.0) {
+            continue;
+        }
+        let Some(catalog) = catalogs.get(&catalog_handle.0) else {
+            continue;
+        };
+
+        if engine.replace_catalog(Arc::new(catalog.clone())) {
+            info!("🔄 Resource catalog reloaded");
         } else {
-            (0, 0)  // Default
+            info!("🔄 Resource catalog changed its resource kinds - they apply from the next game");
         }
-    } else {
+    }
+}
+
+pub fn handle_player_movement(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut player_query: Query<(&mut Transform, &mut Player), With<PlayerMarker>>,
+    mut engine: ResMut<GameEngine>,
+    layout: Res<TileLayout>,
+    mut route: ResMut<PlannedRoute>,
+) {
+    let Ok((mut transform, mut player)) = player_query.get_single_mut() else {
         return;
     };
 
-    // Calculate scan positions ONCE before the loop
-    let player_x = game_data.player_x as i32;
-    let player_y = game_data.player_y as i32;
+    let bindings = [
+        (KeyCode::ArrowUp, KeyCode::KeyW, Heading::North),
+        (KeyCode::ArrowDown, KeyCode::KeyS, Heading::South),
+        (KeyCode::ArrowLeft, KeyCode::KeyA, Heading::West),
+        (KeyCode::ArrowRight, KeyCode::KeyD, Heading::East),
+    ];
 
-    // Center tile in front
-    let distance = 1;
-    let center_x = player_x + direction.0 * distance;
-    let center_y = player_y + direction.1 * distance;
+    for (arrow, letter, heading) in bindings {
+        if !keyboard.just_pressed(arrow) && !keyboard.just_pressed(letter) {
+            continue;
+        }
 
+        // Steering by hand abandons any plotted route
+        route.clear();
+        sail(&mut engine, heading, &mut player, &mut transform, &layout);
+    }
+}
 
-    // Calculate left and right tiles in the scan arc
-    let (left_x, left_y, right_x, right_y) = match direction {
-        (0, 1) => {
-            // Moving UP - scan up-left, up-center, up-right
-            (center_x - 1, center_y, center_x + 1, center_y)
-        }
-        (0, -1) => {
-            // Moving DOWN - scan down-left, down-center, down-right
-            (center_x - 1, center_y, center_x + 1, center_y)
+// Moves the ship one step through the engine and mirrors the result on screen.
+// Returns false when the move was rejected.
+fn sail(engine: &mut GameEngine, heading: Heading, player: &mut Player, transform: &mut Transform, layout: &TileLayout) -> bool {
+    let mut moved = true;
+    for event in engine.apply(Action::Move(heading)) {
+        match event {
+            GameEvent::Moved { x, y, heading, .. } => {
+                player.x = x;
+                player.y = y;
+
+                // Update visual position
+                let world = layout.world_position(x, y);
+                transform.translation.x = world.x;
+                transform.translation.y = world.y;
+                transform.rotation = heading_rotation(heading);
+            }
+            GameEvent::Drifted { x, y } => {
+                info!("🌊 Current carried the ship to ({}, {})", x, y);
+                player.x = x;
+                player.y = y;
+
+                let world = layout.world_position(x, y);
+                transform.translation.x = world.x;
+                transform.translation.y = world.y;
+            }
+            GameEvent::Rejected(reason) => {
+                if reason == RejectReason::Blocked {
+                    info!("🪨 Can't sail through reefs or islands");
+                }
+                moved = false;
+            }
+            _ => {}
         }
-        (1, 0) => {
-            // Moving RIGHT - scan right-up, right-center, right-down
-            (center_x, center_y + 1, center_x, center_y - 1)
+    }
+    moved
+}
+
+// Seconds between steps while sailing a plotted route
+const ROUTE_STEP_SECONDS: f32 = 0.2;
+
+// Left click plots the cheapest route to a tile, clicking the same tile again sets sail.
+// Escape drops the route (and stops the ship if it is already sailing).
+pub fn plan_route_on_click(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    engine: Res<GameEngine>,
+    layout: Res<TileLayout>,
+    mut route: ResMut<PlannedRoute>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        route.clear();
+        return;
+    }
+    if route.sailing || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let (Ok(window), Ok((camera, camera_transform))) = (window_query.get_single(), camera_query.get_single()) else {
+        return;
+    };
+    let Some(target) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+        .and_then(|world| layout.tile_at(world))
+    else {
+        return;
+    };
+
+    if route.target == Some(target) && !route.headings.is_empty() {
+        route.sailing = true;
+        route.step_timer = Timer::from_seconds(ROUTE_STEP_SECONDS, TimerMode::Repeating);
+        return;
+    }
+
+    match engine.find_path(target.0, target.1) {
+        Some(headings) if !headings.is_empty() => {
+            route.preview = engine.preview_route(&headings);
+            route.target = Some(target);
+            route.headings = headings;
         }
-        (-1, 0) => {
-            // Moving LEFT - scan left-up, left-center, left-down
-            (center_x, center_y + 1, center_x, center_y - 1)
+        Some(_) => route.clear(),
+        None => {
+            info!("🪨 No route to ({}, {})", target.0, target.1);
+            route.clear();
         }
-        _ => (center_x, center_y, center_x, center_y), // Shouldn't happen
+    }
+}
+
+// Sails a confirmed route one step at a time, through the same moves as the keyboard
+pub fn sail_planned_route(
+    time: Res<Time>,
+    mut route: ResMut<PlannedRoute>,
+    mut player_query: Query<(&mut Transform, &mut Player), With<PlayerMarker>>,
+    mut engine: ResMut<GameEngine>,
+    layout: Res<TileLayout>,
+) {
+    if !route.sailing || !route.step_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Ok((mut transform, mut player)) = player_query.get_single_mut() else {
+        return;
     };
 
-    // Auto-reveal current tile AND scan 3 tiles ahead in facing direction
-    for (entity, mut sprite, mut tile_state, resource_type, tile) in tiles.iter_mut() {
-        let tile_x = tile.x as i32;
-        let tile_y = tile.y as i32;
+    let heading = route.headings.remove(0);
+    if !sail(&mut engine, heading, &mut player, &mut transform, &layout) || route.headings.is_empty() {
+        route.clear();
+        return;
+    }
+    route.preview = engine.preview_route(&route.headings);
+}
 
-        let mut should_reveal = false;
+// Draws the plotted route, its destination and the hidden tiles it will scan
+pub fn draw_planned_route(route: Res<PlannedRoute>, layout: Res<TileLayout>, mut gizmos: Gizmos) {
+    let Some((x, y)) = route.target else {
+        return;
+    };
+    let color = if route.sailing {
+        Color::srgb(0.3, 0.9, 0.4)
+    } else {
+        Color::srgb(1.0, 0.85, 0.2)
+    };
 
-        // Always reveal tile player is on
-        if tile_x == player_x && tile_y == player_y {
-            should_reveal = true;
-        }
+    gizmos.linestrip_2d(
+        route.preview.waypoints.iter().map(|&(x, y)| layout.world_position(x, y)),
+        color,
+    );
+    gizmos.circle_2d(layout.world_position(x, y), layout.tile_size * 0.3, color);
+
+    for &(x, y) in &route.preview.uncovered {
+        gizmos.rect_2d(
+            layout.world_position(x, y),
+            0.0,
+            Vec2::splat(layout.tile_size * 0.8),
+            Color::srgba(0.6, 0.9, 1.0, 0.6),
+        );
+    }
+}
 
-        // Check if tile is in scan area
-        if (tile_x == center_x && tile_y == center_y) ||
-           (tile_x == left_x && tile_y == left_y) ||
-           (tile_x == right_x && tile_y == right_y) {
-            should_reveal = true;
+// Shows tiles the engine has revealed (current tile, the scan arc ahead of the ship and uncovered hidden waste)
+pub fn auto_reveal_current_tile(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut tiles: Query<(Entity, &mut Sprite, &mut TileState, &Tile)>,
+    engine: Res<GameEngine>,
+    layout: Res<TileLayout>,
+) {
+    let tile_size = layout.tile_size;
+    for (entity, mut sprite, mut tile_state, tile) in tiles.iter_mut() {
+        // Redraw on first reveal, and again when hidden waste gets uncovered
+        let state = engine.tile(tile.x, tile.y);
+        if !state.revealed || (tile_state.revealed && tile_state.hidden == state.hidden) {
+            continue;
         }
+        tile_state.revealed = true;
+        tile_state.hidden = state.hidden;
 
-        if should_reveal && !tile_state.revealed {
-            tile_state.revealed = true;
-            game_data.tiles_revealed_count += 1;  // Track exploration
+        let resource_type = engine.visible_resource_at(tile.x, tile.y);
+        let kind = engine.catalog().kind(resource_type);
 
-            // Reveal tile - change to island/terrain appearance
-            if matches!(resource_type, ResourceType::Empty) {
-                sprite.color = Color::srgb(0.3, 0.5, 0.7); // Water for empty tiles
-                sprite.custom_size = Some(Vec2::new(64.0, 64.0));
-            } else {
-                sprite.color = Color::srgb(0.8, 0.7, 0.5); // Sandy terrain for resources
-                sprite.custom_size = Some(Vec2::new(60.0, 60.0)); // Slightly smaller to show border
-
-                // Check if it's a critical resource
-                if matches!(resource_type,
-                    ResourceType::IdleGPUCluster |
-                    ResourceType::OverprovisionedOpenShift |
-                    ResourceType::MissedReservation
-                ) {
-                    info!("⚠️ Critical resource found: {}", resource_type.hint());
-                }
+        // Reveal tile - change to island/terrain appearance
+        if resource_type.is_empty() {
+            sprite.color = Color::srgb(0.3, 0.5, 0.7); // Water for empty tiles
+            sprite.custom_size = Some(Vec2::splat(tile_size));
+        } else {
+            sprite.color = sandy_color(state.decay_multiplier); // Sandy terrain for resources
+            sprite.custom_size = Some(Vec2::splat(tile_size - 4.0)); // Slightly smaller to show border
+
+            if kind.is_critical() {
+                info!("⚠️ Critical resource found: {}", kind.hint);
             }
+        }
 
-            // Add visual indicator for the resource type
-            if !matches!(resource_type, ResourceType::Empty) {
-                let tile_size = 64.0;
-                let grid_offset_x = -(game_data.grid_size as f32 * tile_size) / 2.0 + tile_size / 2.0;
-                let grid_offset_y = -(game_data.grid_size as f32 * tile_size) / 2.0 + tile_size / 2.0;
-                let world_x = grid_offset_x + (tile.x as f32 * tile_size);
-                let world_y = grid_offset_y + (tile.y as f32 * tile_size);
-
-                // Load the appropriate sprite for this resource type
-                let sprite_path = resource_type.get_sprite_path();
-                if !sprite_path.is_empty() {
-                    commands.spawn((
-                        SpriteBundle {
-                            texture: asset_server.load(sprite_path.to_string()),
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::new(30.0, 30.0)),
-                                ..default()
-                            },
-                            transform: Transform::from_xyz(world_x, world_y, 5.0),
+        // Add visual indicator for the resource type
+        if !resource_type.is_empty() {
+            let world = layout.world_position(tile.x, tile.y);
+
+            // Load the appropriate sprite for this resource type
+            if !kind.sprite.is_empty() {
+                commands.spawn((
+                    SpriteBundle {
+                        texture: asset_server.load(kind.sprite.clone()),
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(tile_size * 0.47)),
                             ..default()
                         },
-                        TileIcon { parent: entity },
-                    ));
-                }
+                        transform: Transform::from_xyz(world.x, world.y, 5.0),
+                        ..default()
+                    },
+                    TileIcon { parent: entity },
+                ));
             }
+
+            commands.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 12.0,
+                            color: Color::srgb(0.5, 0.15, 0.1),
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_xyz(world.x, world.y - tile_size * 0.36, 6.0),
+                    ..default()
+                },
+                DecayLabel { x: tile.x, y: tile.y },
+                TileIcon { parent: entity },
+            ));
         }
     }
 }
 
-pub fn process_tile_fix(
-    mut tiles: Query<(&mut TileState, &ResourceType, &Tile)>,
+// Resource tiles bake from sand towards rust as their waste decays
+fn sandy_color(decay_multiplier: f32) -> Color {
+    let t = (decay_multiplier - 1.0).clamp(0.0, 1.0);
+    Color::srgb(0.8 - 0.1 * t, 0.7 - 0.3 * t, 0.5 - 0.25 * t)
+}
+
+// Shows each unfixed resource's decay level and the sprints until it next grows
+pub fn update_decay_labels(engine: Res<GameEngine>, mut labels: Query<(&mut Text, Ref<DecayLabel>)>) {
+    let sprint = engine.data().sprint;
+    for (mut text, label) in labels.iter_mut() {
+        if !engine.is_changed() && !label.is_added() {
+            continue;
+        }
+
+        let tile = engine.tile(label.x, label.y);
+        let level = (tile.decay_multiplier > 1.0).then(|| format!("x{:.2}", tile.decay_multiplier));
+        let next_growth = engine.next_decay_at(label.x, label.y).map(|next| format!("({})", next - sprint));
+        text.sections[0].value = if tile.fixed {
+            String::new()
+        } else {
+            level.into_iter().chain(next_growth).collect::<Vec<_>>().join(" ")
+        };
+    }
+}
+
+// F3 toggles a debug overlay with every resource tile's effective neighborhood multiplier
+pub fn toggle_neighborhood_overlay(
+    mut commands: Commands,
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut game_data: ResMut<GameData>,
+    engine: Res<GameEngine>,
+    layout: Res<TileLayout>,
+    labels: Query<Entity, With<NeighborhoodLabel>>,
 ) {
-    if !keyboard.just_pressed(KeyCode::Space) {
+    if !keyboard.just_pressed(KeyCode::F3) {
         return;
     }
-
-    // Fix the resource - simple and always succeeds
-    for (mut tile_state, resource_type, tile) in tiles.iter_mut() {
-        if tile.x == game_data.player_x && tile.y == game_data.player_y {
-            if tile_state.revealed && !tile_state.fixed && tile_state.fixing_sprints_left == 0 {
-                tile_state.fixing_sprints_left = resource_type.fix_sprints();
-                game_data.process_sprint();
-            }
-            break;
+    if !labels.is_empty() {
+        for entity in labels.iter() {
+            commands.entity(entity).despawn_recursive();
         }
+        return;
     }
-}
 
-pub fn update_game_state(
-    mut tiles: Query<(&mut TileState, &ResourceType, &mut Sprite, &Tile)>,
-    mut game_data: ResMut<GameData>,
-) {
-    // Apply decay every 5 sprints - simple version
-    if game_data.sprint > 0 && game_data.sprint - game_data.last_decay_sprint >= 5 {
-        game_data.last_decay_sprint = game_data.sprint;
-
-        // Apply 20% decay to all unfixed resources
-        for (mut tile_state, resource_type, _, _) in tiles.iter_mut() {
-            if !tile_state.fixed && !matches!(resource_type, ResourceType::Empty) {
-                if tile_state.decay_multiplier == 0.0 {
-                    tile_state.decay_multiplier = 1.0; // Initialize if not set
-                }
-                tile_state.decay_multiplier *= 1.2; // 20% worse
+    for x in 0..engine.grid_width() {
+        for y in 0..engine.grid_height() {
+            if engine.resource_at(x, y).is_empty() {
+                continue;
             }
+            let world = layout.world_position(x, y);
+            commands.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 13.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_xyz(world.x, world.y + layout.tile_size * 0.36, 7.0),
+                    ..default()
+                },
+                NeighborhoodLabel { x, y },
+                MapBackground,
+            ));
         }
     }
+}
 
-    let mut total_waste = 0;
-    let mut total_monthly_savings = 0;
-    let mut tiles_to_fix = Vec::new();
+pub fn update_neighborhood_overlay(engine: Res<GameEngine>, mut labels: Query<(&mut Text, Ref<NeighborhoodLabel>)>) {
+    for (mut text, label) in labels.iter_mut() {
+        if !engine.is_changed() && !label.is_added() {
+            continue;
+        }
 
-    // Collect all tile positions and states for neighborhood calculations
-    let mut tile_states = Vec::new();
-    for (tile_state, resource_type, _, tile) in tiles.iter() {
-        tile_states.push((
-            tile.x as i32,
-            tile.y as i32,
-            tile_state.fixed,
-            !matches!(resource_type, ResourceType::Empty)
-        ));
+        let multiplier = engine.neighborhood_multiplier(label.x, label.y);
+        let section = &mut text.sections[0];
+        section.value = if engine.tile(label.x, label.y).fixed {
+            String::new()
+        } else {
+            format!("n x{:.2}", multiplier)
+        };
+        section.style.color = if multiplier > 1.0 {
+            Color::srgb(1.0, 0.4, 0.3)
+        } else if multiplier < 1.0 {
+            Color::srgb(0.4, 1.0, 0.5)
+        } else {
+            Color::WHITE
+        };
     }
+}
 
-    // Collect current state for adjacency calculations
-    let mut fixed_positions = Vec::new();
-    for (tile_state, _, _, tile) in tiles.iter() {
-        if tile_state.fixed {
-            fixed_positions.push((tile.x, tile.y));
-        }
-    }
+pub fn process_tile_fix(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut engine: ResMut<GameEngine>,
+    layout: Res<TileLayout>,
+) {
+    let action = if keyboard.just_pressed(KeyCode::Space) {
+        Action::Fix
+    } else if keyboard.just_pressed(KeyCode::Enter) {
+        Action::AdvanceSprint
+    } else {
+        return;
+    };
 
-    // Process tiles
-    for (mut tile_state, resource_type, mut sprite, tile) in tiles.iter_mut() {
-        // Process fixing animations
-        if tile_state.fixing_sprints_left > 0 {
-            tile_state.fixing_sprints_left -= 1;
-            if tile_state.fixing_sprints_left == 0 {
-                tile_state.fixed = true;
-                sprite.color = Color::srgb(0.3, 0.7, 0.4); // Green for fixed
-                sprite.custom_size = Some(Vec2::new(56.0, 56.0)); // Even smaller to show it's complete
-                tiles_to_fix.push((tile.x, tile.y, *resource_type));
+    for event in engine.apply(action) {
+        let catalog = engine.catalog();
+        match event {
+            GameEvent::Rejected(reason) => info!("{:?} rejected: {:?}", action, reason),
+            GameEvent::FixFailed { resource, .. } => info!("💥 Fix failed: {}", catalog.kind(resource).hint),
+            GameEvent::CascadeOutage { x, y, resource } => {
+                info!("🔥 Cascading outage at ({}, {}): {}", x, y, catalog.kind(resource).hint)
             }
-        }
-
-        // Count all fixed tiles' savings for display
-        if tile_state.fixed {
-            let (min_savings, max_savings) = resource_type.savings_range();
-            let savings = (min_savings + max_savings) / 2;
-            total_monthly_savings += savings;
-        }
-
-        // Count ALL unfixed waste with decay and neighborhood effects
-        if !tile_state.fixed && !matches!(resource_type, ResourceType::Empty) {
-            let base_waste = resource_type.waste_cost();
-            let decay_mult = if tile_state.decay_multiplier == 0.0 { 1.0 } else { tile_state.decay_multiplier };
-
-            // Calculate neighborhood effect
-            let mut neighbor_multiplier = 1.0;
-            let tile_x = tile.x as i32;
-            let tile_y = tile.y as i32;
-
-            for (nx, ny, is_fixed, has_resource) in &tile_states {
-                // Check if it's an adjacent tile (not diagonal, not self)
-                let dx = (tile_x - nx).abs();
-                let dy = (tile_y - ny).abs();
-                if (dx == 1 && dy == 0) || (dx == 0 && dy == 1) {
-                    if *has_resource {
-                        if *is_fixed {
-                            neighbor_multiplier *= 0.9; // Fixed neighbor reduces waste by 10%
-                        } else {
-                            neighbor_multiplier *= 1.1; // Unfixed neighbor increases waste by 10%
-                        }
-                    }
+            GameEvent::HiddenWasteUncovered { resource, waste, .. } => {
+                info!("🔍 Hidden waste uncovered: {} (${}/mo)", catalog.kind(resource).hint, waste)
+            }
+            GameEvent::MonthClosed { month, credited } => {
+                info!("📅 Month {} closed: ${} realized savings credited", month, credited);
+                if credited > 0 {
+                    let ship = layout.world_position(engine.data().player_x, engine.data().player_y);
+                    commands.spawn((
+                        Text2dBundle {
+                            text: Text::from_section(
+                                format!("Month {} closed: +${}", month, credited),
+                                TextStyle {
+                                    font_size: 22.0,
+                                    color: Color::srgb(0.2, 0.9, 0.8),
+                                    ..default()
+                                },
+                            ),
+                            transform: Transform::from_xyz(ship.x, ship.y + 44.0, 20.0),
+                            ..default()
+                        },
+                        FloatingText {
+                            timer: Timer::from_seconds(2.0, TimerMode::Once),
+                        },
+                    ));
                 }
             }
-
-            let final_waste = (base_waste as f32 * decay_mult * neighbor_multiplier) as i32;
-            total_waste += final_waste;
+            GameEvent::BonusCredit { x, y, amount, .. } => {
+                let world = layout.world_position(x, y);
+
+                commands.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(
+                            format!("+${} credit", amount),
+                            TextStyle {
+                                font_size: 22.0,
+                                color: Color::srgb(1.0, 0.85, 0.2),
+                                ..default()
+                            },
+                        ),
+                        transform: Transform::from_xyz(world.x, world.y + 20.0, 20.0),
+                        ..default()
+                    },
+                    FloatingText {
+                        timer: Timer::from_seconds(1.5, TimerMode::Once),
+                    },
+                ));
+            }
+            _ => {}
         }
     }
+}
 
-    // Verify the total waste matches (this recalculates from scratch)
-    game_data.total_waste = total_waste;
-    game_data.monthly_savings = total_monthly_savings;
-
-    // Process tiles that were just fixed
-    for (x, y, resource_type) in tiles_to_fix {
-        // When fixed, reduce the total waste on the map
-        game_data.total_waste -= resource_type.waste_cost();
-
-        // Calculate combo multiplier
-        if game_data.resources_fixed_in_order.len() > 0 {
-            let last_fixed = game_data.resources_fixed_in_order.last().unwrap();
-            // Combo if fixing same type or related resources
-            if resource_type == *last_fixed || are_resources_related(&resource_type, last_fixed) {
-                game_data.combo_multiplier = (game_data.combo_multiplier + 0.2).min(2.0);
-            } else {
-                game_data.combo_multiplier = 1.0;
-            }
+pub fn update_game_state(
+    mut tiles: Query<(&mut TileState, &mut Sprite, &Tile)>,
+    engine: Res<GameEngine>,
+    layout: Res<TileLayout>,
+) {
+    // Mirror engine tile state onto the tile entities
+    for (mut tile_state, mut sprite, tile) in tiles.iter_mut() {
+        let state = engine.tile(tile.x, tile.y);
+        if state.fixed && !tile_state.fixed {
+            sprite.color = Color::srgb(0.3, 0.7, 0.4); // Green for fixed
+            sprite.custom_size = Some(Vec2::splat(layout.tile_size - 8.0)); // Even smaller to show it's complete
         }
-        game_data.resources_fixed_in_order.push(resource_type);
 
-        // Check for adjacency bonus
-        let adjacent_fixed = count_adjacent_fixed_from_positions(&fixed_positions, x, y, game_data.grid_size);
-        if adjacent_fixed > 0 {
-            game_data.adjacency_bonus += 50 * adjacent_fixed as i32;
+        let in_outage = state.outage_sprints_left > 0;
+        let decayed = state.decay_multiplier != tile_state.decay_multiplier;
+        let visible = !engine.visible_resource_at(tile.x, tile.y).is_empty();
+        if tile_state.revealed && visible && !state.fixed && (decayed || in_outage != (tile_state.outage_sprints_left > 0)) {
+            sprite.color = if in_outage {
+                Color::srgb(0.9, 0.35, 0.25) // Burning red during a cascading outage
+            } else {
+                sandy_color(state.decay_multiplier) // Back to sandy terrain
+            };
         }
 
-        // Add savings with combo multiplier
-        let (min_savings, max_savings) = resource_type.savings_range();
-        let base_savings = (min_savings + max_savings) / 2;
-        let savings = (base_savings as f32 * game_data.combo_multiplier) as i32;
-        game_data.monthly_savings += savings;
-    }
-
-    // Only check win condition if game is in progress
-    if !game_data.game_lost && !game_data.game_won {
-        game_data.check_win_condition();
+        tile_state.fixed = state.fixed;
+        tile_state.fixing_sprints_left = state.fixing_sprints_left;
+        tile_state.decay_multiplier = state.decay_multiplier;
+        tile_state.outage_sprints_left = state.outage_sprints_left;
     }
 }
 
 pub fn check_win_condition(
-    game_data: Res<GameData>,
+    engine: Res<GameEngine>,
     mut next_state: ResMut<NextState<crate::GameState>>,
 ) {
-    if game_data.game_won || game_data.game_lost {
+    if engine.is_over() {
         next_state.set(crate::GameState::GameOver);
     }
 }
 
-pub fn animate_reveals(
+// Floating text drifts upwards and fades out
+pub fn animate_floating_text(
     mut commands: Commands,
-    mut query: Query<(Entity, &mut RevealAnimation)>,
+    mut query: Query<(Entity, &mut Transform, &mut Text, &mut FloatingText)>,
     time: Res<Time>,
 ) {
-    for (entity, mut animation) in query.iter_mut() {
-        animation.timer.tick(time.delta());
-        if animation.timer.finished() {
-            commands.entity(entity).remove::<RevealAnimation>();
+    for (entity, mut transform, mut text, mut floating) in query.iter_mut() {
+        floating.timer.tick(time.delta());
+        if floating.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation.y += 30.0 * time.delta_seconds();
+        let alpha = 1.0 - floating.timer.fraction();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_alpha(alpha);
         }
     }
 }
 
+// Camera zoom limits (projection scale: below 1 zooms in, above 1 zooms out)
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 4.0;
+
+// Eases the camera towards the ship plus the drag offset, kept over the map.
+// Sailing somewhere snaps the view back onto the ship.
+pub fn camera_follow_player(
+    player_query: Query<Ref<Transform>, (With<PlayerMarker>, Without<GameCamera>)>,
+    mut camera_query: Query<(&mut Transform, &mut GameCamera)>,
+    layout: Res<TileLayout>,
+    time: Res<Time>,
+) {
+    let (Ok(player), Ok((mut transform, mut camera))) = (player_query.get_single(), camera_query.get_single_mut()) else {
+        return;
+    };
+
+    if player.is_changed() {
+        camera.pan = Vec2::ZERO;
+    }
+
+    let half_tile = Vec2::splat(layout.tile_size / 2.0);
+    let min = layout.origin - half_tile;
+    let max = min + layout.map_size();
+    let target = (player.translation.truncate() + camera.pan).clamp(min, max);
+
+    let t = 1.0 - (-8.0 * time.delta_seconds()).exp();
+    let position = transform.translation.truncate().lerp(target, t);
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
+
+// Mouse wheel zooms in and out
+pub fn camera_zoom(
+    mut wheel_events: EventReader<MouseWheel>,
+    mut camera_query: Query<&mut OrthographicProjection, With<GameCamera>>,
+) {
+    let scroll: f32 = wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+    if scroll == 0.0 {
+        return;
+    }
+
+    if let Ok(mut projection) = camera_query.get_single_mut() {
+        projection.scale = (projection.scale * 0.9_f32.powf(scroll)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
+
+// Dragging with the right or middle mouse button pans the view
+pub fn camera_drag_pan(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut GameCamera, &OrthographicProjection)>,
+) {
+    let Ok((mut camera, projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+    let cursor = window_query.get_single().ok().and_then(|window| window.cursor_position());
+    let dragging = mouse.pressed(MouseButton::Right) || mouse.pressed(MouseButton::Middle);
+
+    if let (true, Some(cursor), Some(last)) = (dragging, cursor, camera.last_cursor) {
+        // Screen y points down, world y points up
+        let delta = cursor - last;
+        camera.pan += Vec2::new(-delta.x, delta.y) * projection.scale;
+    }
+    camera.last_cursor = if dragging { cursor } else { None };
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn cleanup_game(
     mut commands: Commands,
     tiles_query: Query<Entity, With<Tile>>,
     player_query: Query<Entity, With<PlayerMarker>>,
     ui_query: Query<Entity, With<GameUI>>,
     icons_query: Query<Entity, With<TileIcon>>,
+    floating_query: Query<Entity, With<FloatingText>>,
+    background_query: Query<Entity, With<MapBackground>>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection, &mut GameCamera)>,
+    mut route: ResMut<PlannedRoute>,
 ) {
     // Remove all tiles
     for entity in tiles_query.iter() {
@@ -586,4 +876,23 @@ pub fn cleanup_game(
     for entity in icons_query.iter() {
         commands.entity(entity).despawn_recursive();
     }
+
+    // Remove floating text
+    for entity in floating_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // Remove ocean and grid lines
+    for entity in background_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    route.clear();
+
+    // Reset the camera for the menus
+    if let Ok((mut transform, mut projection, mut camera)) = camera_query.get_single_mut() {
+        transform.translation = Vec3::new(0.0, 0.0, transform.translation.z);
+        projection.scale = 1.0;
+        *camera = GameCamera::default();
+    }
 }
\ No newline at end of file
@@ -0,0 +1,317 @@
+use std::ops::Range;
+use std::sync::Arc;
+use serde::Serialize;
+use crate::catalog::ResourceCatalog;
+use crate::config::{ConfigError, GameConfig};
+use crate::resources::{Difficulty, DifficultySettings};
+use crate::simulator::run_matchup;
+use crate::strategy::StrategyKind;
+
+// Searches a preset's settings for values that land its win rate in a target band.
+// Knobs are tuned one at a time (coordinate descent), each by bisection between its bounds.
+// Every evaluation replays the same seeds, so the win rate only moves when a knob does.
+
+// Preset knobs the tuner is allowed to move
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum TuneParameter {
+    Budget,
+    BurnCoefficient,
+    MoveCost,
+    ResourceDensity,
+}
+
+impl TuneParameter {
+    pub const ALL: [TuneParameter; 4] = [
+        Self::Budget,
+        Self::BurnCoefficient,
+        Self::MoveCost,
+        Self::ResourceDensity,
+    ];
+
+    // Matches the field name in the config file
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Budget => "budget",
+            Self::BurnCoefficient => "burn_coefficient",
+            Self::MoveCost => "move_cost",
+            Self::ResourceDensity => "resource_density",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn get(&self, settings: &DifficultySettings) -> f32 {
+        match self {
+            Self::Budget => settings.budget as f32,
+            Self::BurnCoefficient => settings.burn_coefficient,
+            Self::MoveCost => settings.move_cost as f32,
+            Self::ResourceDensity => settings.resource_density,
+        }
+    }
+
+    pub fn set(&self, settings: &mut DifficultySettings, value: f32) {
+        match self {
+            Self::Budget => settings.budget = value.round() as i32,
+            Self::BurnCoefficient => settings.burn_coefficient = round_to(value, 0.005),
+            Self::MoveCost => settings.move_cost = value.round() as i32,
+            Self::ResourceDensity => settings.resource_density = round_to(value, 0.005),
+        }
+    }
+
+    // Bisection stops once the bounds are this close, since finer steps don't change anything
+    fn resolution(&self) -> f32 {
+        match self {
+            Self::Budget => 50.0,
+            Self::BurnCoefficient | Self::ResourceDensity => 0.005,
+            Self::MoveCost => 1.0,
+        }
+    }
+}
+
+fn round_to(value: f32, step: f32) -> f32 {
+    ((value / step).round() * step * 1000.0).round() / 1000.0
+}
+
+// A knob and the range it may move in
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct TuneBounds {
+    pub parameter: TuneParameter,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl TuneBounds {
+    // Both ends must make a config the game accepts, or every run spent tuning towards them
+    // is thrown away when the result is written
+    pub fn validate(&self, config: &GameConfig, difficulty: Difficulty) -> Result<(), ConfigError> {
+        for value in [self.min, self.max] {
+            let mut candidate = config.clone();
+            self.parameter.set(candidate.settings_mut(difficulty), value);
+            candidate.validate()?;
+        }
+        Ok(())
+    }
+}
+
+// One evaluation in the search, for the convergence log
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct TuneStep {
+    pub difficulty: Difficulty,
+    pub round: u32,
+    pub parameter: TuneParameter,
+    pub value: f32,
+    pub settings: DifficultySettings,
+    pub win_rate: f32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TuneResult {
+    pub difficulty: Difficulty,
+    pub settings: DifficultySettings,
+    pub win_rate: f32,
+    pub converged: bool,
+    pub evaluations: u32,
+}
+
+pub struct Tuner {
+    pub strategy: StrategyKind,
+    pub catalog: Arc<ResourceCatalog>,
+    pub seeds: Range<u64>,
+    pub threads: usize,
+    pub target: (f32, f32),
+    pub bounds: Vec<TuneBounds>,
+    pub max_rounds: u32,  // Passes over every knob before giving up
+    pub bisection_steps: u32,  // Evaluations per knob per pass, on top of its two bounds
+}
+
+impl Tuner {
+    // Tunes one preset of `config`, reporting every evaluation to `on_step` as it happens
+    pub fn tune(&self, config: &GameConfig, difficulty: Difficulty, mut on_step: impl FnMut(&TuneStep)) -> TuneResult {
+        let mut settings = config.settings(difficulty);
+        let mut evaluations = 0;
+        let mut evaluate = |round: u32, parameter: TuneParameter, settings: DifficultySettings| {
+            let mut candidate = config.clone();
+            *candidate.settings_mut(difficulty) = settings;
+            let candidate = Arc::new(candidate);
+            let matchup = run_matchup(
+                difficulty,
+                &[self.strategy],
+                self.seeds.clone(),
+                &candidate,
+                &self.catalog,
+                self.threads,
+                None,
+            );
+            let step = TuneStep {
+                difficulty,
+                round,
+                parameter,
+                value: parameter.get(&settings),
+                settings,
+                win_rate: matchup.reports[0].win_rate,
+            };
+            evaluations += 1;
+            on_step(&step);
+            step.win_rate
+        };
+
+        let Some(first) = self.bounds.first() else {
+            let win_rate = evaluate(0, TuneParameter::Budget, settings);
+            return self.result(difficulty, settings, win_rate, evaluations);
+        };
+        let mut win_rate = evaluate(0, first.parameter, settings);
+
+        for round in 1..=self.max_rounds {
+            if self.in_band(win_rate) {
+                break;
+            }
+            let before = settings;
+            for bounds in &self.bounds {
+                (settings, win_rate) = self.bisect(round, bounds, settings, win_rate, &mut evaluate);
+                if self.in_band(win_rate) {
+                    break;
+                }
+            }
+            // Another pass would replay the same evaluations
+            if settings == before {
+                break;
+            }
+        }
+        self.result(difficulty, settings, win_rate, evaluations)
+    }
+
+    // Moves one knob to wherever between its bounds comes closest to the middle of the band.
+    // Win rates are assumed to move one way as the knob turns, and which way is read off the bounds.
+    fn bisect(
+        &self,
+        round: u32,
+        bounds: &TuneBounds,
+        settings: DifficultySettings,
+        win_rate: f32,
+        evaluate: &mut impl FnMut(u32, TuneParameter, DifficultySettings) -> f32,
+    ) -> (DifficultySettings, f32) {
+        let parameter = bounds.parameter;
+        let goal = (self.target.0 + self.target.1) / 2.0;
+        let with = |value: f32| {
+            let mut candidate = settings;
+            parameter.set(&mut candidate, value);
+            candidate
+        };
+
+        let mut best = (settings, win_rate);
+        let consider = |candidate: DifficultySettings, rate: f32, best: &mut (DifficultySettings, f32)| {
+            if (rate - goal).abs() < (best.1 - goal).abs() {
+                *best = (candidate, rate);
+            }
+        };
+
+        let (mut low, mut high) = (bounds.min, bounds.max);
+        let low_rate = evaluate(round, parameter, with(low));
+        consider(with(low), low_rate, &mut best);
+        let high_rate = evaluate(round, parameter, with(high));
+        consider(with(high), high_rate, &mut best);
+
+        // The band is out of this knob's reach, so settle for the closer bound
+        if (goal - low_rate) * (goal - high_rate) > 0.0 {
+            return best;
+        }
+
+        let rising = high_rate >= low_rate;
+        for _ in 0..self.bisection_steps {
+            if self.in_band(best.1) || high - low <= parameter.resolution() {
+                break;
+            }
+            let middle = (low + high) / 2.0;
+            let rate = evaluate(round, parameter, with(middle));
+            consider(with(middle), rate, &mut best);
+            if (rate < goal) == rising {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        best
+    }
+
+    fn in_band(&self, win_rate: f32) -> bool {
+        win_rate >= self.target.0 && win_rate <= self.target.1
+    }
+
+    fn result(&self, difficulty: Difficulty, settings: DifficultySettings, win_rate: f32, evaluations: u32) -> TuneResult {
+        TuneResult {
+            difficulty,
+            settings,
+            win_rate,
+            converged: self.in_band(win_rate),
+            evaluations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuner() -> Tuner {
+        Tuner {
+            strategy: StrategyKind::Greedy,
+            catalog: ResourceCatalog::builtin_shared(),
+            seeds: 0..1,
+            threads: 1,
+            target: (20.0, 30.0),
+            bounds: Vec::new(),
+            max_rounds: 3,
+            bisection_steps: 8,
+        }
+    }
+
+    fn settings() -> DifficultySettings {
+        GameConfig::builtin().settings(Difficulty::Normal)
+    }
+
+    // Win rate climbs 1% per $100 of budget above $5,000
+    fn by_budget(_: u32, _: TuneParameter, settings: DifficultySettings) -> f32 {
+        ((settings.budget - 5000) as f32 / 100.0).clamp(0.0, 100.0)
+    }
+
+    #[test]
+    fn bisect_converges_into_the_band() {
+        let tuner = tuner();
+        let bounds = TuneBounds { parameter: TuneParameter::Budget, min: 5000.0, max: 15000.0 };
+        let start = settings();
+        let (settings, win_rate) = tuner.bisect(1, &bounds, start, by_budget(0, bounds.parameter, start), &mut by_budget);
+        assert!(tuner.in_band(win_rate));
+        assert_eq!(win_rate, by_budget(0, bounds.parameter, settings));
+    }
+
+    #[test]
+    fn bisect_settles_for_the_closer_bound_out_of_reach() {
+        let tuner = tuner();
+        let bounds = TuneBounds { parameter: TuneParameter::Budget, min: 5000.0, max: 6000.0 };
+        let mut start = settings();
+        start.budget = 5500;
+        let mut evaluations = 0;
+        let (settings, win_rate) = tuner.bisect(1, &bounds, start, 5.0, &mut |round, parameter, settings| {
+            evaluations += 1;
+            by_budget(round, parameter, settings)
+        });
+        assert_eq!(evaluations, 2);
+        assert_eq!(settings.budget, 6000);
+        assert_eq!(win_rate, 10.0);
+    }
+
+    #[test]
+    fn bisect_follows_a_falling_knob() {
+        let tuner = tuner();
+        let bounds = TuneBounds { parameter: TuneParameter::BurnCoefficient, min: 0.0, max: 0.5 };
+        // Win rate drops as the burn climbs
+        let mut by_burn = |_: u32, _: TuneParameter, settings: DifficultySettings| 100.0 - settings.burn_coefficient * 200.0;
+        let mut start = settings();
+        start.burn_coefficient = 0.1;
+        let (settings, win_rate) = tuner.bisect(1, &bounds, start, 80.0, &mut by_burn);
+        assert!(tuner.in_band(win_rate));
+        assert!((0.35..=0.4).contains(&settings.burn_coefficient));
+    }
+}
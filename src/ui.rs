@@ -1,12 +1,33 @@
 use bevy::prelude::*;
 use crate::components::*;
-use crate::resources::*;
+use crate::config::{current_config, GameConfig, GameConfigHandle};
+use crate::engine::{GameEngine, RejectReason};
+use crate::resources::{Difficulty, GameRng, MapSize, PlannedRoute};
+use crate::solver::{SolveGoal, Solver, PAR_NODE_LIMIT};
 
 const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
 const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
 const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
+const SELECTED_BUTTON: Color = Color::srgb(0.55, 0.4, 0.1);
+// Game states a par search run in slices explores per frame, a few milliseconds' worth
+const PAR_NODES_PER_FRAME: u64 = 100;
 
-pub fn setup_menu(mut commands: Commands) {
+type MenuButtonQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor, &'static MenuButton),
+    (Changed<Interaction>, With<Button>),
+>;
+
+pub fn setup_menu(
+    mut commands: Commands,
+    rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+    map_size: Res<MapSize>,
+    configs: Res<Assets<GameConfig>>,
+    config_handle: Res<GameConfigHandle>,
+) {
+    let config = current_config(&configs, &config_handle);
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -42,6 +63,89 @@ pub fn setup_menu(mut commands: Commands) {
                 ..default()
             }));
 
+            parent.spawn((
+                TextBundle::from_section(
+                    seed_label(rng.seed()),
+                    TextStyle {
+                        font_size: 20.0,
+                        color: Color::srgb(0.6, 0.8, 1.0),
+                        ..default()
+                    },
+                ),
+                SeedText,
+            ));
+
+            // Difficulty selector
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for option in Difficulty::ALL {
+                        parent
+                            .spawn(ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(130.0),
+                                    height: Val::Px(40.0),
+                                    margin: UiRect::horizontal(Val::Px(5.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: difficulty_button_color(option, *difficulty, Interaction::None).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    option.name(),
+                                    TextStyle {
+                                        font_size: 20.0,
+                                        color: Color::srgb(0.9, 0.9, 0.9),
+                                        ..default()
+                                    },
+                                ));
+                            })
+                            .insert(DifficultyButton { difficulty: option });
+                    }
+                });
+
+            parent.spawn((
+                TextBundle::from_section(
+                    difficulty_label(&config, *difficulty),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::srgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(10.0)),
+                    ..default()
+                }),
+                DifficultyText,
+            ));
+
+            parent.spawn((
+                TextBundle::from_section(
+                    map_size_label(&config, *map_size, *difficulty),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::srgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(6.0)),
+                    ..default()
+                }),
+                MapSizeText,
+            ));
+
             parent
                 .spawn(ButtonBundle {
                     style: Style {
@@ -73,10 +177,7 @@ pub fn setup_menu(mut commands: Commands) {
 }
 
 pub fn menu_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &MenuButton),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut interaction_query: MenuButtonQuery,
     mut next_state: ResMut<NextState<crate::GameState>>,
     keyboard: Res<ButtonInput<KeyCode>>,
 ) {
@@ -105,13 +206,146 @@ pub fn menu_system(
     }
 }
 
+fn seed_label(seed: u64) -> String {
+    format!("Seed: {}  (type digits, Backspace to delete, R for random)", seed)
+}
+
+// Lets players type a seed on the menu to replay a specific map
+pub fn seed_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut rng: ResMut<GameRng>,
+    mut text_query: Query<&mut Text, With<SeedText>>,
+) {
+    let mut seed = rng.seed();
+
+    for key in keyboard.get_just_pressed() {
+        let digit = match key {
+            KeyCode::Digit0 | KeyCode::Numpad0 => 0,
+            KeyCode::Digit1 | KeyCode::Numpad1 => 1,
+            KeyCode::Digit2 | KeyCode::Numpad2 => 2,
+            KeyCode::Digit3 | KeyCode::Numpad3 => 3,
+            KeyCode::Digit4 | KeyCode::Numpad4 => 4,
+            KeyCode::Digit5 | KeyCode::Numpad5 => 5,
+            KeyCode::Digit6 | KeyCode::Numpad6 => 6,
+            KeyCode::Digit7 | KeyCode::Numpad7 => 7,
+            KeyCode::Digit8 | KeyCode::Numpad8 => 8,
+            KeyCode::Digit9 | KeyCode::Numpad9 => 9,
+            KeyCode::Backspace => {
+                seed /= 10;
+                continue;
+            }
+            KeyCode::KeyR => {
+                seed = GameRng::from_entropy().seed();
+                continue;
+            }
+            _ => continue,
+        };
+        seed = seed.saturating_mul(10).saturating_add(digit);
+    }
+
+    if seed != rng.seed() {
+        *rng = GameRng::new(seed);
+        if let Ok(mut text) = text_query.get_single_mut() {
+            text.sections[0].value = seed_label(seed);
+        }
+    }
+}
+
+fn difficulty_label(config: &GameConfig, difficulty: Difficulty) -> String {
+    let settings = config.settings(difficulty);
+    format!(
+        "Budget ${} | {}x{} map | Move ${} | Burn {:.0}% of waste  (Left/Right to change)",
+        settings.budget,
+        settings.grid_width,
+        settings.grid_height,
+        settings.move_cost,
+        settings.burn_coefficient * 100.0
+    )
+}
+
+fn map_size_label(config: &GameConfig, map_size: MapSize, difficulty: Difficulty) -> String {
+    let (width, height) = map_size.dimensions(&config.settings(difficulty));
+    format!("Map: {} {}x{}  (M to change, budget scales with area)", map_size.name(), width, height)
+}
+
+fn difficulty_button_color(option: Difficulty, selected: Difficulty, interaction: Interaction) -> Color {
+    match interaction {
+        Interaction::Pressed => PRESSED_BUTTON,
+        Interaction::Hovered => HOVERED_BUTTON,
+        Interaction::None if option == selected => SELECTED_BUTTON,
+        Interaction::None => NORMAL_BUTTON,
+    }
+}
+
+// Difficulty buttons plus Left/Right arrows pick the preset for the next game
+pub fn difficulty_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut difficulty: ResMut<Difficulty>,
+    configs: Res<Assets<GameConfig>>,
+    config_handle: Res<GameConfigHandle>,
+    mut buttons: Query<(&Interaction, &mut BackgroundColor, &DifficultyButton)>,
+    mut text_query: Query<&mut Text, With<DifficultyText>>,
+) {
+    let mut selected = *difficulty;
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        selected = selected.next();
+    }
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        selected = selected.previous();
+    }
+    for (interaction, _, button) in &buttons {
+        if *interaction == Interaction::Pressed {
+            selected = button.difficulty;
+        }
+    }
+
+    if selected != *difficulty {
+        *difficulty = selected;
+        if let Ok(mut text) = text_query.get_single_mut() {
+            text.sections[0].value = difficulty_label(&current_config(&configs, &config_handle), selected);
+        }
+    }
+
+    for (interaction, mut color, button) in &mut buttons {
+        *color = difficulty_button_color(button.difficulty, selected, *interaction).into();
+    }
+}
+
+// M cycles through map sizes for the next game
+pub fn map_size_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    difficulty: Res<Difficulty>,
+    mut map_size: ResMut<MapSize>,
+    configs: Res<Assets<GameConfig>>,
+    config_handle: Res<GameConfigHandle>,
+    mut text_query: Query<&mut Text, With<MapSizeText>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        *map_size = map_size.next();
+    }
+
+    // The preset map follows the difficulty, so refresh on either change
+    if map_size.is_changed() || difficulty.is_changed() {
+        if let Ok(mut text) = text_query.get_single_mut() {
+            text.sections[0].value = map_size_label(&current_config(&configs, &config_handle), *map_size, *difficulty);
+        }
+    }
+}
+
 pub fn cleanup_menu(mut commands: Commands, query: Query<Entity, With<GameUI>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
     }
 }
 
-pub fn setup_ui(mut commands: Commands) {
+pub fn setup_ui(
+    mut commands: Commands,
+    rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+    configs: Res<Assets<GameConfig>>,
+    config_handle: Res<GameConfigHandle>,
+) {
+    let move_cost = current_config(&configs, &config_handle).settings(*difficulty).move_cost;
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -215,6 +449,51 @@ pub fn setup_ui(mut commands: Commands) {
         })
         .insert(GameUI);
 
+    // Seed and difficulty of the current map, for bug reports and replays
+    commands
+        .spawn(TextBundle::from_section(
+            format!("Seed: {} | {}", rng.seed(), difficulty.name()),
+            TextStyle {
+                font_size: 16.0,
+                color: Color::srgb(0.6, 0.6, 0.7),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(84.0),
+            right: Val::Px(10.0),
+            ..default()
+        }))
+        .insert(GameUI);
+
+    // Forecast panel: runway, next decay and what fixing the current tile would change
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(108.0),
+                right: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            background_color: Color::srgba(0.1, 0.1, 0.2, 0.85).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::srgb(0.85, 0.85, 0.9),
+                        ..default()
+                    },
+                ),
+                ForecastText,
+            ));
+        })
+        .insert(GameUI);
+
     // Current tile info panel - moved higher to avoid overlap
     commands
         .spawn(NodeBundle {
@@ -247,6 +526,26 @@ pub fn setup_ui(mut commands: Commands) {
         })
         .insert(GameUI);
 
+    // Plotted route preview, empty until a tile is clicked
+    commands
+        .spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::srgb(1.0, 0.85, 0.2),
+                    ..default()
+                },
+            ).with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(115.0),
+                left: Val::Percent(5.0),
+                ..default()
+            }),
+            RouteText,
+        ))
+        .insert(GameUI);
+
     // Controls panel
     commands
         .spawn(NodeBundle {
@@ -264,9 +563,12 @@ pub fn setup_ui(mut commands: Commands) {
         })
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
-                "Move: Arrow/WASD ($100 + auto-scan) | Fix: Space (may fail on critical resources)",
+                format!(
+                    "Move: Arrow/WASD (${} + auto-scan, x2 in storms) | Fix: Space | End Sprint: Enter\nClick a tile to plot a route, again to sail | Reefs & islands block | Currents » push | Critical fixes may fail",
+                    move_cost
+                ),
                 TextStyle {
-                    font_size: 20.0,
+                    font_size: 18.0,
                     color: Color::srgb(0.7, 0.7, 0.7),
                     ..default()
                 },
@@ -275,9 +577,97 @@ pub fn setup_ui(mut commands: Commands) {
         .insert(GameUI);
 }
 
+pub fn update_forecast_ui(engine: Res<GameEngine>, mut forecast_query: Query<&mut Text, With<ForecastText>>) {
+    if !engine.is_changed() {
+        return;
+    }
+    let Ok(mut text) = forecast_query.get_single_mut() else {
+        return;
+    };
+
+    let game_data = engine.data();
+    let burn_rate = game_data.calculate_burn_rate();
+    let mut forecast = format!(
+        "📅 Month {} (closes after sprint {}) | Banked ${}\n📉 Runway: {} sprints at ${}/turn\n",
+        game_data.month(),
+        game_data.month() * game_data.config.sprints_per_month,
+        game_data.realized_savings,
+        game_data.runway_sprints(),
+        burn_rate,
+    );
+    match engine.next_decay_sprint() {
+        Some(next_decay) => forecast.push_str(&format!(
+            "⏳ Next decay: sprint {} ({} to go)\n",
+            next_decay,
+            next_decay.saturating_sub(game_data.sprint),
+        )),
+        None => forecast.push_str("⏳ No more decay ahead\n"),
+    }
+
+    match engine.evaluate_fix() {
+        Ok(projection) => {
+            forecast.push_str(&format!(
+                "🔧 If fixed ({} sprint{}{}):\n   Burn ${}/turn ({:+})\n   Waste {:.1}% | Savings ~${}/mo\n   Combo {:.1}x",
+                projection.sprints,
+                if projection.sprints > 1 { "s" } else { "" },
+                if projection.failure_chance > 0.0 {
+                    format!(", {:.0}% fail", projection.failure_chance * 100.0)
+                } else {
+                    String::new()
+                },
+                projection.burn_rate,
+                projection.burn_rate - burn_rate,
+                projection.waste_percentage,
+                projection.monthly_savings,
+                projection.combo_multiplier,
+            ));
+        }
+        Err(RejectReason::FixInProgress) => forecast.push_str("🔧 Fix under way here"),
+        Err(RejectReason::AlreadyFixed) => forecast.push_str("🔧 Already fixed"),
+        Err(RejectReason::NotRevealed) => forecast.push_str("🔧 Unexplored - scan it first"),
+        Err(_) => forecast.push_str("🔧 Nothing to fix here"),
+    }
+
+    text.sections[0].value = forecast;
+}
+
+pub fn update_route_text(
+    route: Res<PlannedRoute>,
+    engine: Res<GameEngine>,
+    mut route_query: Query<&mut Text, With<RouteText>>,
+) {
+    if !route.is_changed() {
+        return;
+    }
+    let Ok(mut text) = route_query.get_single_mut() else {
+        return;
+    };
+
+    let preview = &route.preview;
+    let moves = route.headings.len();
+    text.sections[0].value = if route.target.is_none() {
+        String::new()
+    } else if route.sailing {
+        format!("⛵ Sailing: {} move{} left, ${} | Esc to stop", moves, if moves == 1 { "" } else { "s" }, preview.cost)
+    } else {
+        let mut info = format!(
+            "🧭 Route: {} move{}, ${} | Scans {} new tile{} | Click again to sail, Esc to cancel",
+            moves,
+            if moves == 1 { "" } else { "s" },
+            preview.cost,
+            preview.uncovered.len(),
+            if preview.uncovered.len() == 1 { "" } else { "s" },
+        );
+        if preview.cost >= engine.data().budget {
+            info.push_str(" | ⚠ Over budget!");
+        }
+        info
+    };
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn update_ui(
-    game_data: Res<GameData>,
-    tiles: Query<(&TileState, &ResourceType, &Tile)>,
+    engine: Res<GameEngine>,
     mut budget_query: Query<&mut Text, (With<BudgetText>, Without<SprintText>, Without<WasteText>, Without<SavingsText>, Without<BurnRateText>, Without<ComboText>, Without<CurrentTileText>)>,
     mut sprint_query: Query<&mut Text, (With<SprintText>, Without<BudgetText>, Without<WasteText>, Without<SavingsText>, Without<BurnRateText>, Without<ComboText>, Without<CurrentTileText>)>,
     mut waste_query: Query<&mut Text, (With<WasteText>, Without<BudgetText>, Without<SprintText>, Without<SavingsText>, Without<BurnRateText>, Without<ComboText>, Without<CurrentTileText>)>,
@@ -286,6 +676,8 @@ pub fn update_ui(
     mut combo_query: Query<&mut Text, (With<ComboText>, Without<BudgetText>, Without<SprintText>, Without<WasteText>, Without<SavingsText>, Without<BurnRateText>, Without<CurrentTileText>)>,
     mut current_tile_query: Query<&mut Text, (With<CurrentTileText>, Without<BudgetText>, Without<SprintText>, Without<WasteText>, Without<SavingsText>, Without<BurnRateText>, Without<ComboText>)>,
 ) {
+    let game_data = engine.data();
+
     if let Ok(mut text) = budget_query.get_single_mut() {
         text.sections[0].value = format!("Budget: ${}", game_data.budget);
         text.sections[0].style.color = if game_data.budget < 2000 {
@@ -303,7 +695,12 @@ pub fn update_ui(
 
     if let Ok(mut text) = waste_query.get_single_mut() {
         let waste_pct = game_data.waste_percentage();
-        text.sections[0].value = format!("Waste: {:.1}%", waste_pct);
+        text.sections[0].value = if game_data.undiscovered_waste > 0 {
+            // Hidden resources are billed before anyone finds them
+            format!("Waste: {:.1}% (${}/mo undiscovered)", waste_pct, game_data.undiscovered_waste)
+        } else {
+            format!("Waste: {:.1}%", waste_pct)
+        };
         text.sections[0].style.color = if waste_pct > 30.0 {
             Color::srgb(0.8, 0.2, 0.2)
         } else if waste_pct > 15.0 {
@@ -350,40 +747,56 @@ pub fn update_ui(
 
     // Update current tile info
     if let Ok(mut text) = current_tile_query.get_single_mut() {
-        let mut current_tile_info = "Current Location: Unknown".to_string();
-
-        // Find the tile the player is on
-        for (tile_state, resource_type, tile) in tiles.iter() {
-            if tile.x == game_data.player_x && tile.y == game_data.player_y {
-                if tile_state.revealed {
-                    if matches!(resource_type, ResourceType::Empty) {
-                        current_tile_info = "Current Location: Clear waters - nothing to fix".to_string();
-                    } else {
-                        let hint = resource_type.hint();
-                        let (min_savings, max_savings) = resource_type.savings_range();
-                        let fix_time = resource_type.fix_sprints();
-
-                        if tile_state.fixed {
-                            current_tile_info = format!("✓ Fixed: {} (Saving ${}-${}/mo)", hint, min_savings, max_savings);
-                        } else if tile_state.fixing_sprints_left > 0 {
-                            current_tile_info = format!("⚙ Fixing: {} ({} sprints left)", hint, tile_state.fixing_sprints_left);
-                        } else {
-                            current_tile_info = format!("⚠ {}: Fix {} sprint{} → Save ${}-${}/mo",
-                                hint, fix_time, if fix_time > 1 { "s" } else { "" }, min_savings, max_savings);
-                        }
-                    }
-                } else {
-                    current_tile_info = "Current Location: Unexplored territory".to_string();
+        let tile_state = engine.tile(game_data.player_x, game_data.player_y);
+        let resource_type = engine.visible_resource_at(game_data.player_x, game_data.player_y);
+        let kind = engine.catalog().kind(resource_type);
+
+        let current_tile_info = if !tile_state.revealed {
+            "Current Location: Unexplored territory".to_string()
+        } else if resource_type.is_empty() {
+            "Current Location: Clear waters - nothing to fix".to_string()
+        } else {
+            let hint = &kind.hint;
+            let (min_savings, max_savings) = kind.savings_range;
+            let fix_time = kind.fix_sprints;
+
+            if tile_state.fixed {
+                format!(
+                    "✓ Fixed: {} (Saving ${}/mo vs ${}/mo estimated, range ${}-${})",
+                    hint, tile_state.savings_actual, tile_state.savings_estimate, min_savings, max_savings
+                )
+            } else if tile_state.fixing_sprints_left > 0 {
+                format!("⚙ Fixing: {} ({} sprints left - Enter to end sprint)", hint, tile_state.fixing_sprints_left)
+            } else {
+                let mut info = format!("⚠ {}: Fix {} sprint{} → Save ${}-${}/mo",
+                    hint, fix_time, if fix_time > 1 { "s" } else { "" }, min_savings, max_savings);
+                let failure_chance = kind.fix_failure_chance;
+                if failure_chance > 0.0 {
+                    info.push_str(&format!(" | Risk: {:.0}% fail", failure_chance * 100.0));
+                }
+                if tile_state.outage_sprints_left > 0 {
+                    info.push_str(&format!(" | 🔥 Outage {} sprints", tile_state.outage_sprints_left));
                 }
-                break;
+                if tile_state.decay_multiplier > 1.0 {
+                    info.push_str(&format!(" | Decay x{:.2}", tile_state.decay_multiplier));
+                }
+                if let Some(next_decay) = engine.next_decay_at(game_data.player_x, game_data.player_y) {
+                    info.push_str(&format!(" (grows in {})", next_decay - game_data.sprint));
+                }
+                info
             }
-        }
+        };
 
         text.sections[0].value = current_tile_info;
     }
 }
 
-pub fn setup_game_over(mut commands: Commands, game_data: Res<GameData>) {
+pub fn setup_game_over(mut commands: Commands, engine: Res<GameEngine>) {
+    let game_data = engine.data();
+    // The best this seed allows, knowing the whole map and every roll from the start. The par
+    // line fills in when the search is done.
+    let solver = Solver { goal: SolveGoal::Fastest, node_limit: PAR_NODE_LIMIT };
+    let par = start_par_search(solver, engine.restart());
     let (title, color) = if game_data.game_won {
         ("Arr! Ye Plundered the Treasure!", Color::srgb(0.8, 0.7, 0.2))
     } else {
@@ -414,6 +827,20 @@ pub fn setup_game_over(mut commands: Commands, game_data: Res<GameData>) {
                 },
             ));
 
+            if let Some(victory) = game_data.victory {
+                parent.spawn(TextBundle::from_section(
+                    format!("{}: {}", victory.name(), victory.description(&game_data.config.victory)),
+                    TextStyle {
+                        font_size: 26.0,
+                        color: Color::srgb(1.0, 0.8, 0.2),
+                        ..default()
+                    },
+                ).with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }));
+            }
+
             parent.spawn(TextBundle::from_section(
                 format!("Final Budget: ${}", game_data.budget),
                 TextStyle {
@@ -427,7 +854,7 @@ pub fn setup_game_over(mut commands: Commands, game_data: Res<GameData>) {
             }));
 
             parent.spawn(TextBundle::from_section(
-                format!("Monthly Savings: ${}", game_data.monthly_savings),
+                format!("Monthly Savings: ${} (${} banked)", game_data.monthly_savings, game_data.realized_savings),
                 TextStyle {
                     font_size: 24.0,
                     color: Color::WHITE,
@@ -438,6 +865,78 @@ pub fn setup_game_over(mut commands: Commands, game_data: Res<GameData>) {
                 ..default()
             }));
 
+            if let Some(accuracy) = game_data.estimate_accuracy() {
+                parent.spawn(TextBundle::from_section(
+                    format!(
+                        "Estimate Accuracy: {:.0}% (${}/mo actual vs ${}/mo estimated)",
+                        accuracy * 100.0, game_data.monthly_savings, game_data.estimated_savings
+                    ),
+                    TextStyle {
+                        font_size: 24.0,
+                        color: Color::srgb(0.7, 0.8, 0.9),
+                        ..default()
+                    },
+                ).with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }));
+            }
+
+            if game_data.bonus_credits_earned > 0 {
+                parent.spawn(TextBundle::from_section(
+                    format!("Bonus Credits: +${}", game_data.bonus_credits_earned),
+                    TextStyle {
+                        font_size: 24.0,
+                        color: Color::srgb(1.0, 0.85, 0.2),
+                        ..default()
+                    },
+                ).with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }));
+            }
+
+            if game_data.hidden_waste_discovered > 0 {
+                parent.spawn(TextBundle::from_section(
+                    format!("Hidden Waste Uncovered: ${}/mo", game_data.hidden_waste_discovered),
+                    TextStyle {
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ).with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }));
+            }
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "Par: charting the best course...",
+                    TextStyle {
+                        font_size: 24.0,
+                        color: Color::srgb(0.7, 0.9, 0.7),
+                        ..default()
+                    },
+                ).with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }),
+                ParText(par),
+            ));
+
+            parent.spawn(TextBundle::from_section(
+                format!("Seed: {} | {}", engine.seed(), game_data.difficulty.name()),
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::srgb(0.6, 0.8, 1.0),
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::all(Val::Px(10.0)),
+                ..default()
+            }));
+
             parent
                 .spawn(ButtonBundle {
                     style: Style {
@@ -468,20 +967,54 @@ pub fn setup_game_over(mut commands: Commands, game_data: Res<GameData>) {
         .insert(GameOverUI);
 }
 
+#[cfg(not(target_family = "wasm"))]
+fn start_par_search(solver: Solver, start: GameEngine) -> ParSearch {
+    use std::sync::{Arc, Mutex};
+    let par = Arc::new(Mutex::new(None));
+    let slot = par.clone();
+    bevy::tasks::AsyncComputeTaskPool::get()
+        .spawn(async move {
+            let solution = solver.solve(&start);
+            *slot.lock().unwrap() = Some(solution);
+        })
+        .detach();
+    ParSearch::Background(par)
+}
+
+// The web build runs tasks on the main thread, where a whole search would freeze the screen
+#[cfg(target_family = "wasm")]
+fn start_par_search(solver: Solver, start: GameEngine) -> ParSearch {
+    ParSearch::Sliced(Box::new(solver.start(&start)))
+}
+
+// Swaps in par once the search has finished; the component goes with it so this runs once
+pub fn update_par_text(mut commands: Commands, mut query: Query<(Entity, &mut ParText, &mut Text)>) {
+    for (entity, mut par, mut text) in &mut query {
+        let solution = match &mut par.0 {
+            ParSearch::Background(slot) => slot.lock().unwrap().take(),
+            ParSearch::Sliced(run) => run.run(PAR_NODES_PER_FRAME),
+        };
+        let Some(solution) = solution else {
+            continue;
+        };
+        text.sections[0].value = solution.par();
+        commands.entity(entity).remove::<ParText>();
+    }
+}
+
 pub fn game_over_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &MenuButton),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut interaction_query: MenuButtonQuery,
     mut next_state: ResMut<NextState<crate::GameState>>,
+    mut rng: ResMut<GameRng>,
 ) {
     for (interaction, mut color, button) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
                 *color = PRESSED_BUTTON.into();
-                match button.action {
-                    MenuAction::Play => next_state.set(crate::GameState::Playing),
-                    _ => {}
+                if let MenuAction::Play = button.action {
+                    // New voyage, new map
+                    *rng = GameRng::from_entropy();
+                    next_state.set(crate::GameState::Playing);
                 }
             }
             Interaction::Hovered => {
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use crate::resources::GameData;
+
+// The four victory paths from the design document. Each is a `WinCondition`, and a
+// `WinConditions` registry holds the ones a difficulty preset enables. Which paths are
+// enabled and where their thresholds sit both come from the game config.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VictoryType {
+    Elimination,
+    Savings,
+    ComboMaster,
+    Balanced,
+}
+
+impl VictoryType {
+    pub const ALL: [VictoryType; 4] = [
+        Self::Elimination,
+        Self::Savings,
+        Self::ComboMaster,
+        Self::Balanced,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Elimination => "Elimination Victory",
+            Self::Savings => "Savings Victory",
+            Self::ComboMaster => "Combo Master",
+            Self::Balanced => "Balanced Victory",
+        }
+    }
+
+    pub fn description(&self, thresholds: &VictoryThresholds) -> String {
+        let t = thresholds;
+        match self {
+            Self::Elimination => format!("Total waste cut to ${} or less", t.elimination_max_waste),
+            Self::Savings => format!(
+                "${}/mo savings with waste at or below {:.0}%",
+                t.savings_target, t.savings_max_waste_percentage
+            ),
+            Self::ComboMaster => format!("{:.1}x combo with waste at or below ${}", t.combo_target, t.combo_max_waste),
+            Self::Balanced => format!(
+                "Budget over ${}, savings over ${}, waste at or below {:.0}%",
+                t.balanced_min_budget, t.balanced_min_savings, t.balanced_max_waste_percentage
+            ),
+        }
+    }
+}
+
+// Where each victory path's bar sits (set in the game config)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VictoryThresholds {
+    pub elimination_max_waste: i32,
+    pub savings_target: i32,  // Monthly savings needed for a Savings Victory
+    pub savings_max_waste_percentage: f32,
+    pub combo_target: f32,
+    pub combo_max_waste: i32,
+    pub balanced_min_budget: i32,  // Budget, savings and waste must all clear their bars
+    pub balanced_min_savings: i32,
+    pub balanced_max_waste_percentage: f32,
+}
+
+impl Default for VictoryThresholds {
+    fn default() -> Self {
+        Self {
+            elimination_max_waste: 100,
+            savings_target: 1500,
+            savings_max_waste_percentage: 40.0,
+            combo_target: 1.8,
+            combo_max_waste: 300,
+            balanced_min_budget: 1000,
+            balanced_min_savings: 800,
+            balanced_max_waste_percentage: 50.0,
+        }
+    }
+}
+
+// The victory paths a preset enables. Written as a list in the config file, and checked in
+// `VictoryType::ALL` order whatever order the list is in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<VictoryType>", into = "Vec<VictoryType>")]
+pub struct VictorySet(u8);
+
+impl VictorySet {
+    pub const NONE: VictorySet = VictorySet(0);
+
+    pub fn all() -> Self {
+        VictoryType::ALL.into_iter().collect()
+    }
+
+    pub fn contains(&self, victory: VictoryType) -> bool {
+        self.0 & Self::bit(victory) != 0
+    }
+
+    pub fn with(self, victory: VictoryType) -> Self {
+        VictorySet(self.0 | Self::bit(victory))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = VictoryType> + '_ {
+        VictoryType::ALL.into_iter().filter(|&victory| self.contains(victory))
+    }
+
+    fn bit(victory: VictoryType) -> u8 {
+        1 << VictoryType::ALL.iter().position(|&v| v == victory).unwrap_or(0)
+    }
+}
+
+// Every path, so configs written before the list existed keep all four
+impl Default for VictorySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FromIterator<VictoryType> for VictorySet {
+    fn from_iter<I: IntoIterator<Item = VictoryType>>(victories: I) -> Self {
+        victories.into_iter().fold(Self::NONE, Self::with)
+    }
+}
+
+impl From<Vec<VictoryType>> for VictorySet {
+    fn from(victories: Vec<VictoryType>) -> Self {
+        victories.into_iter().collect()
+    }
+}
+
+impl From<VictorySet> for Vec<VictoryType> {
+    fn from(set: VictorySet) -> Self {
+        set.iter().collect()
+    }
+}
+
+pub trait WinCondition: Send + Sync {
+    fn victory_type(&self) -> VictoryType;
+    fn is_met(&self, data: &GameData) -> bool;
+}
+
+pub struct Elimination {
+    pub max_waste: i32,
+}
+
+impl WinCondition for Elimination {
+    fn victory_type(&self) -> VictoryType {
+        VictoryType::Elimination
+    }
+
+    fn is_met(&self, data: &GameData) -> bool {
+        data.total_waste <= self.max_waste
+    }
+}
+
+pub struct SavingsTarget {
+    pub target: i32,
+    pub max_waste_percentage: f32,
+}
+
+impl WinCondition for SavingsTarget {
+    fn victory_type(&self) -> VictoryType {
+        VictoryType::Savings
+    }
+
+    fn is_met(&self, data: &GameData) -> bool {
+        data.monthly_savings >= self.target && data.waste_percentage() <= self.max_waste_percentage
+    }
+}
+
+pub struct ComboMaster {
+    pub target: f32,
+    pub max_waste: i32,
+}
+
+impl WinCondition for ComboMaster {
+    fn victory_type(&self) -> VictoryType {
+        VictoryType::ComboMaster
+    }
+
+    fn is_met(&self, data: &GameData) -> bool {
+        data.combo_multiplier >= self.target && data.total_waste <= self.max_waste
+    }
+}
+
+pub struct Balanced {
+    pub min_budget: i32,
+    pub min_savings: i32,
+    pub max_waste_percentage: f32,
+}
+
+impl WinCondition for Balanced {
+    fn victory_type(&self) -> VictoryType {
+        VictoryType::Balanced
+    }
+
+    fn is_met(&self, data: &GameData) -> bool {
+        data.budget > self.min_budget
+            && data.monthly_savings > self.min_savings
+            && data.waste_percentage() <= self.max_waste_percentage
+    }
+}
+
+// Registry of enabled win conditions, checked in registration order
+#[derive(Default)]
+pub struct WinConditions {
+    conditions: Vec<Box<dyn WinCondition>>,
+}
+
+impl WinConditions {
+    pub fn with(mut self, condition: impl WinCondition + 'static) -> Self {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+
+    // The paths in `enabled`, with their bars at `thresholds`
+    pub fn from_config(enabled: VictorySet, thresholds: &VictoryThresholds) -> Self {
+        let t = thresholds;
+        enabled.iter().fold(Self::default(), |conditions, victory| match victory {
+            VictoryType::Elimination => conditions.with(Elimination { max_waste: t.elimination_max_waste }),
+            VictoryType::Savings => conditions.with(SavingsTarget {
+                target: t.savings_target,
+                max_waste_percentage: t.savings_max_waste_percentage,
+            }),
+            VictoryType::ComboMaster => conditions.with(ComboMaster {
+                target: t.combo_target,
+                max_waste: t.combo_max_waste,
+            }),
+            VictoryType::Balanced => conditions.with(Balanced {
+                min_budget: t.balanced_min_budget,
+                min_savings: t.balanced_min_savings,
+                max_waste_percentage: t.balanced_max_waste_percentage,
+            }),
+        })
+    }
+
+    // The set a game's preset enables under its config
+    pub fn for_game(data: &GameData) -> Self {
+        Self::from_config(data.settings.win_conditions, &data.config.victory)
+    }
+
+    pub fn check(&self, data: &GameData) -> Option<VictoryType> {
+        self.conditions
+            .iter()
+            .find(|c| c.is_met(data))
+            .map(|c| c.victory_type())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Difficulty;
+
+    #[test]
+    fn only_enabled_paths_are_checked() {
+        let mut data = GameData::new(Difficulty::Normal);
+        data.total_waste = 0;
+        let thresholds = VictoryThresholds::default();
+
+        let all = WinConditions::from_config(VictorySet::all(), &thresholds);
+        assert_eq!(all.check(&data), Some(VictoryType::Elimination));
+        let savings_only = WinConditions::from_config(VictorySet::NONE.with(VictoryType::Savings), &thresholds);
+        assert_eq!(savings_only.check(&data), None);
+    }
+
+    #[test]
+    fn thresholds_come_from_the_config() {
+        let mut data = GameData::new(Difficulty::Normal);
+        data.total_waste = 150;
+        let enabled = VictorySet::NONE.with(VictoryType::Elimination);
+
+        assert_eq!(WinConditions::from_config(enabled, &VictoryThresholds::default()).check(&data), None);
+        let lenient = VictoryThresholds {
+            elimination_max_waste: 200,
+            ..Default::default()
+        };
+        assert_eq!(WinConditions::from_config(enabled, &lenient).check(&data), Some(VictoryType::Elimination));
+    }
+
+    #[test]
+    fn victory_set_reads_as_a_list() {
+        let set: VictorySet = ron::from_str("[Balanced, Elimination]").unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![VictoryType::Elimination, VictoryType::Balanced]);
+        assert_eq!(ron::to_string(&set).unwrap(), "[Elimination,Balanced]");
+    }
+}